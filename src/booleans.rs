@@ -1,10 +1,14 @@
 use std::marker::PhantomData;
 
-use crate::{numbers::{Number, Zero, Successor}, lists::StrRepr};
+use crate::{functions::{self, AllTrue, AnyTrue, Function, Identity}, numbers::{GEQZero, Number, PeanoEqual, Zero, Successor, N1}, lists::{Cons, List, Nil, StrRepr}};
 
 pub struct True;
 pub struct False;
 
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a type-level Bool",
+    label = "expected `True` or `False`"
+)]
 pub trait Bool {
     const VALUE: bool;
     type EquivalentInt: Number;
@@ -65,16 +69,190 @@ impl<B: Bool> And<B> for False {
     type And = False;
 }
 
+pub trait Xor<Other: Bool> {
+    type Xor: Bool;
+}
+impl<B: Bool> Xor<B> for False {
+    type Xor = B;
+}
+impl<B: Bool + Not> Xor<B> for True {
+    type Xor = B::Not;
+}
+
+pub trait Nand<Other: Bool> {
+    type Nand: Bool;
+}
+impl<A: And<B>, B: Bool> Nand<B> for A
+where
+    A::And: Not,
+{
+    type Nand = <A::And as Not>::Not;
+}
+
+pub trait Nor<Other: Bool> {
+    type Nor: Bool;
+}
+impl<A: Or<B>, B: Bool> Nor<B> for A
+where
+    A::Or: Not,
+{
+    type Nor = <A::Or as Not>::Not;
+}
+
+pub trait Implies<Other: Bool> {
+    type Implies: Bool;
+}
+/// False implies anything
+impl<B: Bool> Implies<B> for False {
+    type Implies = True;
+}
+impl<B: Bool> Implies<B> for True {
+    type Implies = B;
+}
+
+pub trait Iff<Other: Bool> {
+    type Iff: Bool;
+}
+impl<A: Xor<B>, B: Bool> Iff<B> for A
+where
+    A::Xor: Not,
+{
+    type Iff = <A::Xor as Not>::Not;
+}
+
+/// True if every Bool in the list is True, the same as `functions::AllTrue`
+pub trait AllOf: List {
+    type Output: Bool;
+}
+impl<L: List + AllTrue> AllOf for L {
+    type Output = <L as AllTrue>::Output;
+}
+
+/// True if some Bool in the list is True, the same as `functions::AnyTrue`
+pub trait AnyOf: List {
+    type Output: Bool;
+}
+impl<L: List + AnyTrue> AnyOf for L {
+    type Output = <L as AnyTrue>::Output;
+}
+
+/// True if no Bool in the list is True
+pub trait NoneOf: List {
+    type Output: Bool;
+}
+impl<L: AnyOf> NoneOf for L
+where
+    L::Output: Not,
+{
+    type Output = <L::Output as Not>::Not;
+}
+
+/// Number of Trues in the list, `functions::Count` with the Bools as their own predicate
+pub trait Count: List {
+    type Count: Number + GEQZero;
+}
+impl<L: List + functions::Count<Identity>> Count for L {
+    type Count = <L as functions::Count<Identity>>::Output;
+}
+
+/// True if exactly one Bool in the list is True
+pub trait ExactlyOne: List {
+    type Output: Bool;
+}
+impl<L: Count> ExactlyOne for L
+where
+    L::Count: PeanoEqual<N1>,
+{
+    type Output = <L::Count as PeanoEqual<N1>>::Equal;
+}
+
+/// Boolean expressions. Operands are Bool types or parenthesized expressions, e.g.
+/// `logic!(and (or A, B), (not C))`. `all`, `any`, `none`, `exactly_one` and `count`
+/// take a list of Bools
 #[macro_export]
 macro_rules! logic {
-    (and $x:ty, $y:ty) => {
-        <$x as $crate::booleans::And<$y>>::And
+    (@operand ($($inner:tt)*)) => {
+        $crate::logic!($($inner)*)
+    };
+    (@operand $x:ty) => {
+        $x
+    };
+    (@binary $op:ident; ($($x:tt)*), $($y:tt)+) => {
+        <$crate::logic!($($x)*) as $crate::booleans::$op<$crate::logic!(@operand $($y)+)>>::$op
+    };
+    (@binary $op:ident; $x:ty, $($y:tt)+) => {
+        <$x as $crate::booleans::$op<$crate::logic!(@operand $($y)+)>>::$op
+    };
+    (not $($x:tt)+) => {
+        <$crate::logic!(@operand $($x)+) as $crate::booleans::Not>::Not
+    };
+    (and $($args:tt)+) => {
+        $crate::logic!(@binary And; $($args)+)
+    };
+    (or $($args:tt)+) => {
+        $crate::logic!(@binary Or; $($args)+)
+    };
+    (xor $($args:tt)+) => {
+        $crate::logic!(@binary Xor; $($args)+)
+    };
+    (nand $($args:tt)+) => {
+        $crate::logic!(@binary Nand; $($args)+)
+    };
+    (nor $($args:tt)+) => {
+        $crate::logic!(@binary Nor; $($args)+)
+    };
+    (implies $($args:tt)+) => {
+        $crate::logic!(@binary Implies; $($args)+)
     };
-    (or $x:ty, $y:ty) => {
-        <$x as $crate::booleans::Or<$y>>::Or
+    (iff $($args:tt)+) => {
+        $crate::logic!(@binary Iff; $($args)+)
+    };
+    (all $list:ty) => {
+        <$list as $crate::booleans::AllOf>::Output
+    };
+    (any $list:ty) => {
+        <$list as $crate::booleans::AnyOf>::Output
+    };
+    (none $list:ty) => {
+        <$list as $crate::booleans::NoneOf>::Output
+    };
+    (exactly_one $list:ty) => {
+        <$list as $crate::booleans::ExactlyOne>::Output
+    };
+    (count $list:ty) => {
+        <$list as $crate::booleans::Count>::Count
     };
 }
 
+fn connectives_type_test() {
+    use crate::{m, make_list, numbers::{N0, N2}};
+
+    let _: logic!(xor True, False) = True;
+    let _: logic!(xor True, True) = False;
+    let _: logic!(nand True, True) = False;
+    let _: logic!(nand True, False) = True;
+    let _: logic!(nor False, False) = True;
+    let _: logic!(nor False, True) = False;
+    let _: logic!(implies False, False) = True;
+    let _: logic!(implies True, False) = False;
+    let _: logic!(iff False, False) = True;
+    let _: logic!(iff True, False) = False;
+
+    let _: logic!(all Nil) = True;
+    let _: logic!(all make_list!(True, False)) = False;
+    let _: logic!(any make_list!(False, True)) = True;
+    let _: logic!(none make_list!(False, False)) = True;
+    let _: logic!(exactly_one make_list!(False, True, False)) = True;
+    let _: logic!(exactly_one make_list!(True, True)) = False;
+    let _: logic!(count make_list!(True, False, True)) = N2::default();
+    let _: logic!(count Nil) = N0::default();
+
+    let _: logic!(and (or False, True), (not False)) = True;
+    let _: logic!(and (or False, True), (not True)) = False;
+    let _: logic!(not (implies True, (xor True, True))) = True;
+    let _: logic!(or m!(eq N0, N2), (and True, True)) = True;
+}
+
 pub struct If<Cond, V1, V2>(PhantomData<Cond>, PhantomData<V1>, PhantomData<V2>);
 pub trait IfOutput { type Output; }
 impl<V1, V2> IfOutput for If<True, V1, V2> {
@@ -92,6 +270,109 @@ macro_rules! typeif {
     };
 }
 
+/// A deferred value, only computed when a `LazyIf` selects it
+pub trait Force { type Output; }
+/// `F` applied to `Arg`, not evaluated until forced
+pub struct Thunk<F, Arg>(PhantomData<F>, PhantomData<Arg>);
+impl<F: Function<Arg>, Arg> Force for Thunk<F, Arg> {
+    type Output = F::Apply;
+}
+/// A value that needs no evaluation
+pub struct Ready<T>(PhantomData<T>);
+impl<T> Force for Ready<T> {
+    type Output = T;
+}
+
+/// Like `If`, but the branches are `Force` thunks and only the selected one is evaluated,
+/// so the other branch does not have to satisfy any bounds
+pub struct LazyIf<Cond, Then, Else>(PhantomData<Cond>, PhantomData<Then>, PhantomData<Else>);
+pub trait LazyIfOutput { type Output; }
+impl<Then: Force, Else> LazyIfOutput for LazyIf<True, Then, Else> {
+    type Output = Then::Output;
+}
+impl<Then, Else: Force> LazyIfOutput for LazyIf<False, Then, Else> {
+    type Output = Else::Output;
+}
+
+#[macro_export]
+macro_rules! lazyif {
+    ($cond:ty, $on_true:ty, $on_false:ty) => {
+        <$crate::booleans::LazyIf<$cond, $on_true, $on_false> as $crate::booleans::LazyIfOutput>::Output
+    };
+}
+
+fn lazyif_type_test() {
+    use crate::{m, numbers::{PeanoAbsDiff, N0, N3}};
+
+    /// N -> 0 by recursing on N - 1. With `If` the recursive call would also be required at 0,
+    /// and since |0 - 1| = 1 it would never terminate
+    struct ToZero;
+    impl<N: PeanoEqual<Zero> + PeanoAbsDiff<N1>> Function<N> for ToZero
+    where
+        LazyIf<m!(eq N, Zero), Ready<Zero>, Thunk<ToZero, <N as PeanoAbsDiff<N1>>::AbsDiff>>: LazyIfOutput,
+    {
+        type Apply = lazyif!(m!(eq N, Zero), Ready<Zero>, Thunk<ToZero, <N as PeanoAbsDiff<N1>>::AbsDiff>);
+    }
+
+    let _: <ToZero as Function<N3>>::Apply = N0::default();
+    let _: lazyif!(True, Ready<True>, Thunk<ToZero, True>) = True;
+}
+
+/// `Cases` is a list of `(Guard, Result)` pairs. The output is the result of the first
+/// pair whose guard is `True`, or `Default` if there is none
+pub struct Cond<Cases, Default>(PhantomData<Cases>, PhantomData<Default>);
+pub trait CondOutput { type Output; }
+impl<Default> CondOutput for Cond<Nil, Default> {
+    type Output = Default;
+}
+impl<Result, Rest, Default> CondOutput for Cond<Cons<(True, Result), Rest>, Default> {
+    type Output = Result;
+}
+impl<Result, Rest, Default> CondOutput for Cond<Cons<(False, Result), Rest>, Default>
+where
+    Cond<Rest, Default>: CondOutput,
+{
+    type Output = <Cond<Rest, Default> as CondOutput>::Output;
+}
+
+/// `Cond` with one `guard => result` arm per case and `_ => default` last
+///
+/// ```ignore
+/// typematch! {
+///     m!(eq N, N3) => Alive,
+///     logic!(and IsAlive, m!(eq N, N2)) => Alive,
+///     _ => Dead,
+/// }
+/// ```
+#[macro_export]
+macro_rules! typematch {
+    (@cases [$($case:ty,)*] _ => $default:ty $(,)?) => {
+        <$crate::booleans::Cond<$crate::make_list!($($case),*), $default> as $crate::booleans::CondOutput>::Output
+    };
+    (@cases [$($case:ty,)*] $guard:ty => $result:ty, $($rest:tt)*) => {
+        $crate::typematch!(@cases [$($case,)* ($guard, $result),] $($rest)*)
+    };
+    ($($arms:tt)*) => {
+        $crate::typematch!(@cases [] $($arms)*)
+    };
+}
+
+fn typematch_type_test() {
+    use crate::{m, numbers::{N0, N2, N3}};
+
+    type Bucket<N> = typematch! {
+        m!(eq N, N0) => N0,
+        m!(lt N, N3) => N2,
+        _ => N3,
+    };
+    let _: Bucket<N0> = N0::default();
+    let _: Bucket<N2> = N2::default();
+    let _: Bucket<Successor<N3>> = N3::default();
+
+    let _: typematch! { _ => True } = True;
+    let _: typematch! { False => False, logic!(or False, True) => True, _ => False } = True;
+}
+
 /// convert a regular bool into a funny type system bool
 pub struct BoolWrapper<const VAL: bool>;
 pub trait LiftBool {
@@ -0,0 +1,185 @@
+//! enumerating candidates: permutations, combinations, subsets and products of lists
+
+use std::marker::PhantomData;
+
+use crate::{
+    functions::{FlatMap, Function, Map},
+    lists::{Cons, List, ListConcat, Nil},
+    numbers::{GEQZero, Number, Successor, Zero},
+};
+
+/// L -> Cons<X, L>
+pub struct PrependItem<X>(PhantomData<X>);
+impl<X, L> Function<L> for PrependItem<X> {
+    type Apply = Cons<X, L>;
+}
+
+/// [X, X, ..., X] with `Self` items
+pub trait Replicate<X> {
+    type Output: List + ListConcat;
+}
+impl<X> Replicate<X> for Zero {
+    type Output = Nil;
+}
+impl<X, N: Number + GEQZero + Replicate<X>> Replicate<X> for Successor<N> {
+    type Output = Cons<X, N::Output>;
+}
+
+/// Y -> (X, Y)
+pub struct PairWith<X>(PhantomData<X>);
+impl<X, Y> Function<Y> for PairWith<X> {
+    type Apply = (X, Y);
+}
+
+/// X -> [(X, Y) for Y in Other]
+pub struct PairWithAll<Other>(PhantomData<Other>);
+impl<Other: Map<PairWith<X>>, X> Function<X> for PairWithAll<Other>
+where
+    <Other as Map<PairWith<X>>>::Output: ListConcat,
+{
+    type Apply = <Other as Map<PairWith<X>>>::Output;
+}
+
+/// Every pair (X, Y) with X from `Self` and Y from `Other`, in row-major order
+pub trait CartesianProduct<Other> {
+    type Output;
+}
+impl<L: FlatMap<PairWithAll<Other>>, Other> CartesianProduct<Other> for L {
+    type Output = <L as FlatMap<PairWithAll<Other>>>::Output;
+}
+
+/// Subset -> [Subset, Cons<X, Subset>]
+pub struct WithAndWithout<X>(PhantomData<X>);
+impl<X, Subset> Function<Subset> for WithAndWithout<X> {
+    type Apply = Cons<Subset, Cons<Cons<X, Subset>, Nil>>;
+}
+
+/// Every sublist of the list, keeping the order of the items
+pub trait PowerSet {
+    type Output: List + ListConcat;
+}
+impl PowerSet for Nil {
+    type Output = Cons<Nil, Nil>;
+}
+impl<X, XS: PowerSet> PowerSet for Cons<X, XS>
+where
+    XS::Output: FlatMap<WithAndWithout<X>>,
+    <XS::Output as FlatMap<WithAndWithout<X>>>::Output: List + ListConcat,
+{
+    type Output = <XS::Output as FlatMap<WithAndWithout<X>>>::Output;
+}
+
+/// Every non-empty suffix of the list, longest first
+pub trait Tails {
+    type Output: List + ListConcat;
+}
+impl Tails for Nil {
+    type Output = Nil;
+}
+impl<X, XS: Tails> Tails for Cons<X, XS> {
+    type Output = Cons<Cons<X, XS>, XS::Output>;
+}
+
+/// Cons<X, XS> -> the `K` item combinations of XS, each with X in front
+pub struct StartingWith<K>(PhantomData<K>);
+impl<K, X, XS: Combinations<K>> Function<Cons<X, XS>> for StartingWith<K>
+where
+    XS::Output: Map<PrependItem<X>>,
+    <XS::Output as Map<PrependItem<X>>>::Output: List + ListConcat,
+{
+    type Apply = <XS::Output as Map<PrependItem<X>>>::Output;
+}
+
+/// Every sublist of the list with `K` items, keeping the order of the items
+pub trait Combinations<K> {
+    type Output: List + ListConcat;
+}
+impl<L> Combinations<Zero> for L {
+    type Output = Cons<Nil, Nil>;
+}
+/// pick the first item of the combination from every suffix, then the other K from what follows it
+impl<K: Number + GEQZero, L: Tails> Combinations<Successor<K>> for L
+where
+    L::Output: FlatMap<StartingWith<K>>,
+    <L::Output as FlatMap<StartingWith<K>>>::Output: List + ListConcat,
+{
+    type Output = <L::Output as FlatMap<StartingWith<K>>>::Output;
+}
+
+/// Every way to insert `X` into the list
+pub trait Insertions<X> {
+    type Output: List + ListConcat;
+}
+impl<X> Insertions<X> for Nil {
+    type Output = Cons<Cons<X, Nil>, Nil>;
+}
+impl<X, Y, YS: Insertions<X>> Insertions<X> for Cons<Y, YS>
+where
+    YS::Output: Map<PrependItem<Y>>,
+    <YS::Output as Map<PrependItem<Y>>>::Output: ListConcat,
+{
+    type Output = Cons<Cons<X, Cons<Y, YS>>, <YS::Output as Map<PrependItem<Y>>>::Output>;
+}
+
+/// L -> every way to insert X into L
+pub struct InsertEverywhere<X>(PhantomData<X>);
+impl<X, L: Insertions<X>> Function<L> for InsertEverywhere<X> {
+    type Apply = L::Output;
+}
+
+/// Every ordering of the items of the list
+pub trait Permutations {
+    type Output: List + ListConcat;
+}
+impl Permutations for Nil {
+    type Output = Cons<Nil, Nil>;
+}
+impl<X, XS: Permutations> Permutations for Cons<X, XS>
+where
+    XS::Output: FlatMap<InsertEverywhere<X>>,
+    <XS::Output as FlatMap<InsertEverywhere<X>>>::Output: List + ListConcat,
+{
+    type Output = <XS::Output as FlatMap<InsertEverywhere<X>>>::Output;
+}
+
+fn combinatorics_type_test() {
+    use crate::{
+        lists::Length,
+        m, make_list,
+        numbers::{N0, N1, N2, N3, N4, N5, N6, N8, N10, N12, N16},
+    };
+
+    type Three = make_list!(N0, N1, N2);
+    type Four = make_list!(N0, N1, N2, N3);
+    type Five = make_list!(N0, N1, N2, N3, N4);
+    type Two = make_list!(N0, N1);
+    type LengthOf<L> = <L as Length>::Length;
+
+    let _: <N3 as Replicate<N1>>::Output = <make_list!(N1, N1, N1)>::default();
+    let _: LengthOf<<N5 as Replicate<N2>>::Output> = N5::default();
+
+    type Pairs = <Two as CartesianProduct<make_list!(N2, N3)>>::Output;
+    let _: Pairs = <make_list!((N0, N2), (N0, N3), (N1, N2), (N1, N3))>::default();
+    // |A x B| = |A| * |B|
+    let _: LengthOf<<Three as CartesianProduct<Four>>::Output> = N12::default();
+    let _: LengthOf<<Three as CartesianProduct<Nil>>::Output> = N0::default();
+
+    let _: <Two as PowerSet>::Output =
+        <make_list!(Nil, make_list!(N0), make_list!(N1), make_list!(N0, N1))>::default();
+    // 2^n subsets
+    let _: LengthOf<<Nil as PowerSet>::Output> = N1::default();
+    let _: LengthOf<<Four as PowerSet>::Output> = N16::default();
+
+    let _: <Two as Tails>::Output = <make_list!(make_list!(N0, N1), make_list!(N1))>::default();
+    let _: <Three as Combinations<N2>>::Output =
+        <make_list!(make_list!(N0, N1), make_list!(N0, N2), make_list!(N1, N2))>::default();
+    // n! / (k! (n - k)!) combinations
+    let _: LengthOf<<Five as Combinations<N2>>::Output> = N10::default();
+    let _: LengthOf<<Five as Combinations<N0>>::Output> = N1::default();
+    let _: LengthOf<<Four as Combinations<N5>>::Output> = N0::default();
+
+    let _: <Two as Permutations>::Output = <make_list!(make_list!(N0, N1), make_list!(N1, N0))>::default();
+    // n! permutations
+    let _: LengthOf<<Three as Permutations>::Output> = N6::default();
+    let _: LengthOf<<Four as Permutations>::Output> = <m!(add N16, N8)>::default();
+}
@@ -1,13 +1,27 @@
+use std::marker::PhantomData;
+
 use crate::{
-    booleans::{Bool, False, True},
-    lists::{Cons, List, ListConcat, Nil},
-    numbers::{PeanoAbsDiff, Successor, Zero, N1, Number, GEQZero, Negative, GTZero},
+    booleans::{Bool, False, Not, True},
+    lists::{Cons, Length, List, ListConcat, ListConcatAll, Nil, StrRepr, Zip},
+    option::{None, Some, TypeOption},
+    result::{Err, Ok},
+    numbers::{PeanoAdd, Successor, Zero, N1, Number, GEQZero, Negative, GTZero},
 };
 
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be applied to `{T}`",
+    label = "no `Function<{T}>` impl for `{Self}`",
+    note = "check that `{T}` is the kind of argument `{Self}` expects (a Number, a List, a tuple of arguments, ...)"
+)]
 pub trait Function<T> {
     type Apply;
 }
 
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a predicate on `{T}`",
+    label = "`{Self}` applied to `{T}` does not produce a Bool",
+    note = "a predicate is a `Function` whose output is `True` or `False`"
+)]
 pub trait Predicate<T>: Function<T> {
     type BoolApply: Bool;
 }
@@ -15,6 +29,11 @@ impl<T, U: Function<T>> Predicate<T> for U where <Self as Function<T>>::Apply: B
     type BoolApply = <Self as Function<T>>::Apply;
 }
 
+#[diagnostic::on_unimplemented(
+    message = "cannot map `{F}` over `{Self}`",
+    label = "`{F}` cannot be mapped over this",
+    note = "`Map` requires a List whose every element can be passed to `{F}`"
+)]
 pub trait Map<F> {
     type Output: List;
 }
@@ -28,6 +47,11 @@ where
     type Output = Cons<F::Apply, <XS as Map<F>>::Output>;
 }
 
+#[diagnostic::on_unimplemented(
+    message = "cannot filter `{Self}` with `{F}`",
+    label = "`{F}` cannot be used to filter this",
+    note = "`Filter` requires a List, and `{F}` must return `True` or `False` for every element"
+)]
 pub trait Filter<F> {
     type Output;
 }
@@ -43,6 +67,11 @@ where
 }
 
 /// Given a list of lists, map each list using the function, and concat the results together
+#[diagnostic::on_unimplemented(
+    message = "cannot flat-map `{F}` over `{Self}`",
+    label = "`{F}` cannot be flat-mapped over this",
+    note = "`FlatMap` requires a List, and `{F}` must return a List for every element"
+)]
 pub trait FlatMap<F> {
     type Output;
 }
@@ -82,9 +111,27 @@ impl Function<Negative<Successor<Zero>>> for Increment {
     type Apply = Zero;
 }
 
-struct Decrement;
-impl<I: PeanoAbsDiff<N1>> Function<I> for Decrement {
-    type Apply = <I as PeanoAbsDiff<N1>>::AbsDiff;
+/// Error tag of `Decrement` on zero
+#[derive(Default)]
+pub struct Underflow;
+impl StrRepr for Underflow {
+    fn str_repr() -> String {
+        "Decrement of zero".into()
+    }
+}
+
+/// N -> `Ok` of N - 1, or `Err<Underflow>` for zero. Only the naturals bottom out,
+/// negative numbers keep decrementing
+pub struct Decrement;
+impl<I: Number + GEQZero> Function<Successor<I>> for Decrement {
+    type Apply = Ok<I>;
+}
+/// -N - 1 = -(N + 1)
+impl<I: Number + GTZero> Function<Negative<I>> for Decrement {
+    type Apply = Ok<Negative<Successor<I>>>;
+}
+impl Function<Zero> for Decrement {
+    type Apply = Err<Underflow>;
 }
 
 struct IsZero;
@@ -111,6 +158,286 @@ impl<L: List + AnyTrue> AnyTrue for Cons<False, L> {
     type Output = <L as AnyTrue>::Output;
 }
 
+pub trait AllTrue {
+    type Output: Bool;
+}
+impl AllTrue for Nil {
+    type Output = True;
+}
+impl<L: List> AllTrue for Cons<False, L> {
+    type Output = False;
+}
+impl<L: List + AllTrue> AllTrue for Cons<True, L> {
+    type Output = <L as AllTrue>::Output;
+}
+
+/// X -> not P(X)
+pub struct Complement<P>(PhantomData<P>);
+impl<P: Predicate<X>, X> Function<X> for Complement<P>
+where
+    P::BoolApply: Not,
+{
+    type Apply = <P::BoolApply as Not>::Not;
+}
+
+/// The first item that satisfies `P` as `Some<X>`, or `None`. Items after it are not tested
+pub trait Find<P> {
+    type Output: TypeOption;
+}
+impl<P> Find<P> for Nil {
+    type Output = None;
+}
+impl<P: Predicate<X>, X, XS: FindIf<P::BoolApply, P, X>> Find<P> for Cons<X, XS> {
+    type Output = XS::Output;
+}
+
+/// `Find` on the rest of the list once it is known whether `X` satisfies `P`
+pub trait FindIf<Found, P, X> {
+    type Output: TypeOption;
+}
+impl<P, X, XS> FindIf<True, P, X> for XS {
+    type Output = Some<X>;
+}
+impl<P, X, XS: Find<P>> FindIf<False, P, X> for XS {
+    type Output = XS::Output;
+}
+
+/// True if some item satisfies `P`
+pub trait Any<P> {
+    type Output: Bool;
+}
+impl<P, L: Find<P>> Any<P> for L {
+    type Output = <L::Output as TypeOption>::IsSome;
+}
+
+/// True if every item satisfies `P`
+pub trait All<P> {
+    type Output: Bool;
+}
+impl<P, L: Any<Complement<P>>> All<P> for L
+where
+    L::Output: Not,
+{
+    type Output = <L::Output as Not>::Not;
+}
+
+/// Index of the first item that satisfies `P`. Does not compile if there is none
+#[diagnostic::on_unimplemented(
+    message = "no item of `{Self}` satisfies `{P}`",
+    label = "`Position` needs a matching item",
+    note = "use `Find` to handle lists without a matching item"
+)]
+pub trait Position<P> {
+    type Output: Number + GEQZero;
+}
+impl<P: Predicate<X>, X, XS: PositionIf<P::BoolApply, P>> Position<P> for Cons<X, XS> {
+    type Output = XS::Output;
+}
+
+/// `Position` on the rest of the list once it is known whether the current item satisfies `P`
+pub trait PositionIf<Found, P> {
+    type Output: Number + GEQZero;
+}
+impl<P, XS> PositionIf<True, P> for XS {
+    type Output = Zero;
+}
+impl<P, XS: Position<P>> PositionIf<False, P> for XS {
+    type Output = Successor<XS::Output>;
+}
+
+/// Number of items that satisfy `P`
+pub trait Count<P> {
+    type Output: Number + GEQZero;
+}
+impl<P, L: Filter<P>> Count<P> for L
+where
+    L::Output: Length,
+{
+    type Output = <L::Output as Length>::Length;
+}
+
+/// The items that satisfy `P` and the ones that do not, both in their original order
+pub trait Partition<P> {
+    type Matching;
+    type NotMatching;
+}
+impl<P, L: Filter<P> + Filter<Complement<P>>> Partition<P> for L {
+    type Matching = <L as Filter<P>>::Output;
+    type NotMatching = <L as Filter<Complement<P>>>::Output;
+}
+
+fn search_type_test() {
+    use crate::{
+        make_list,
+        numbers::{N0, N2, N3},
+    };
+
+    type Numbers = make_list!(N1, N0, N2, N0);
+    let _: <Numbers as Find<IsZero>>::Output = Some::<N0>::default();
+    let _: <Numbers as Find<Const<False>>>::Output = None;
+    let _: <Numbers as Find<Complement<IsZero>>>::Output = Some::<N1>::default();
+    let _: <Nil as Find<IsZero>>::Output = None;
+
+    let _: <Numbers as Any<IsZero>>::Output = True;
+    let _: <Nil as Any<IsZero>>::Output = False;
+    let _: <Numbers as All<IsZero>>::Output = False;
+    let _: <make_list!(N0, N0) as All<IsZero>>::Output = True;
+
+    let _: <Numbers as Position<IsZero>>::Output = N1::default();
+    let _: <Numbers as Position<Complement<IsZero>>>::Output = N0::default();
+    let _: <Numbers as Count<IsZero>>::Output = N2::default();
+    let _: <Numbers as Count<Const<False>>>::Output = N0::default();
+
+    let _: <Numbers as Partition<IsZero>>::Matching = <make_list!(N0, N0)>::default();
+    let _: <Numbers as Partition<IsZero>>::NotMatching = <make_list!(N1, N2)>::default();
+    let _: <make_list!(N3) as Partition<IsZero>>::Matching = Nil;
+}
+
+/// x -> x
+pub struct Identity;
+impl<T> Function<T> for Identity {
+    type Apply = T;
+}
+
+/// _ -> V
+pub struct Const<V>(PhantomData<V>);
+impl<V, T> Function<T> for Const<V> {
+    type Apply = V;
+}
+
+/// x -> F(G(x))
+pub struct Compose<F, G>(PhantomData<F>, PhantomData<G>);
+impl<F: Function<G::Apply>, G: Function<T>, T> Function<T> for Compose<F, G> {
+    type Apply = F::Apply;
+}
+
+/// (A, B) -> F((B, A))
+pub struct Flip<F>(PhantomData<F>);
+impl<F: Function<(B, A)>, A, B> Function<(A, B)> for Flip<F> {
+    type Apply = F::Apply;
+}
+
+/// B -> F((A, B)), F with its first argument fixed to A
+pub struct Partial1<F, A>(PhantomData<F>, PhantomData<A>);
+impl<F: Function<(A, B)>, A, B> Function<B> for Partial1<F, A> {
+    type Apply = F::Apply;
+}
+
+/// C -> F((A, B, C)), F with its first two arguments fixed to A and B
+pub struct Partial2<F, A, B>(PhantomData<F>, PhantomData<A>, PhantomData<B>);
+impl<F: Function<(A, B, C)>, A, B, C> Function<C> for Partial2<F, A, B> {
+    type Apply = F::Apply;
+}
+
+/// A -> (B -> F((A, B))), F taking its arguments one at a time
+pub struct Curry<F>(PhantomData<F>);
+impl<F, A> Function<A> for Curry<F> {
+    type Apply = Partial1<F, A>;
+}
+
+/// (A, B) -> F(A)(B), the inverse of `Curry`
+pub struct Uncurry<F>(PhantomData<F>);
+impl<F: Function<A>, A, B> Function<(A, B)> for Uncurry<F>
+where
+    F::Apply: Function<B>,
+{
+    type Apply = <F::Apply as Function<B>>::Apply;
+}
+
+/// Apply a two-argument function to the items of two lists of the same length, pairwise
+pub trait ZipWith<F, Other> {
+    type Output: List;
+}
+impl<F, L: Zip<Other>, Other> ZipWith<F, Other> for L
+where
+    L::Output: Map<F>,
+{
+    type Output = <L::Output as Map<F>>::Output;
+}
+
+/// Apply every function of the list `Fs` in order, the first one first
+pub struct Pipe<Fs>(PhantomData<Fs>);
+impl<T> Function<T> for Pipe<Nil> {
+    type Apply = T;
+}
+impl<T, F: Function<T>, Rest> Function<T> for Pipe<Cons<F, Rest>>
+where
+    Pipe<Rest>: Function<F::Apply>,
+{
+    type Apply = <Pipe<Rest> as Function<F::Apply>>::Apply;
+}
+
+/// `Map`, `Filter`, `FlatMap` and `ListConcatAll` as functions on lists, to be used as
+/// stages of a `Pipe`
+pub struct MapWith<F>(PhantomData<F>);
+impl<F, L: Map<F>> Function<L> for MapWith<F> {
+    type Apply = L::Output;
+}
+pub struct FilterWith<F>(PhantomData<F>);
+impl<F, L: Filter<F>> Function<L> for FilterWith<F> {
+    type Apply = L::Output;
+}
+pub struct FlatMapWith<F>(PhantomData<F>);
+impl<F, L: FlatMap<F>> Function<L> for FlatMapWith<F> {
+    type Apply = L::Output;
+}
+pub struct ConcatAll;
+impl<L: ListConcatAll> Function<L> for ConcatAll {
+    type Apply = L::ListConcatAll;
+}
+
+/// `pipe!(X => F, G, H)` is `H(G(F(X)))`
+#[macro_export]
+macro_rules! pipe {
+    ($input:ty => $($stage:ty),+ $(,)?) => {
+        <$crate::functions::Pipe<$crate::make_list!($($stage),+)> as $crate::functions::Function<$input>>::Apply
+    };
+}
+
+fn combinators_type_test() {
+    use crate::{make_list, numbers::{N0, N1, N2, N3}};
+
+    let _: <Identity as Function<N1>>::Apply = N1::default();
+    let _: <Const<N2> as Function<N1>>::Apply = N2::default();
+    let _: <Compose<Increment, Increment> as Function<N1>>::Apply = N3::default();
+    let _: <Flip<Const<N0>> as Function<(N1, N2)>>::Apply = N0::default();
+
+    let _: <Partial1<Flip<Const<N0>>, N1> as Function<N2>>::Apply = N0::default();
+    let _: <Partial1<Uncurry<Curry<Add>>, N1> as Function<N2>>::Apply = N3::default();
+    let _: <Partial2<AddThree, N1, N1> as Function<N1>>::Apply = N3::default();
+    let _: pipe!(make_list!(N0, N1) => MapWith<Partial1<Add, N2>>) = <make_list!(N2, N3)>::default();
+    // a curried function is a function that returns functions
+    type AddOne = <Curry<Add> as Function<N1>>::Apply;
+    let _: <AddOne as Function<N2>>::Apply = N3::default();
+
+    type Sums = <make_list!(N1, N2) as ZipWith<Add, make_list!(N2, N0)>>::Output;
+    let _: Sums = <make_list!(N3, N2)>::default();
+
+    let _: <Pipe<Nil> as Function<N1>>::Apply = N1::default();
+    let _: pipe!(N0 => Increment, Increment, Increment) = N3::default();
+    let _: pipe!(make_list!(N0, N1, N0) => FilterWith<IsZero>, MapWith<Increment>) = <make_list!(N1, N1)>::default();
+    let _: pipe!(make_list!(N1, N2) => FlatMapWith<Compose<Singleton, Increment>>) = <make_list!(N2, N3)>::default();
+}
+
+/// (A, B) -> A + B
+struct Add;
+impl<A: PeanoAdd<B>, B: Number> Function<(A, B)> for Add {
+    type Apply = <A as PeanoAdd<B>>::Val;
+}
+struct AddThree;
+impl<A: PeanoAdd<B>, B: Number, C: Number> Function<(A, B, C)> for AddThree
+where
+    <A as PeanoAdd<B>>::Val: PeanoAdd<C>,
+{
+    type Apply = <<A as PeanoAdd<B>>::Val as PeanoAdd<C>>::Val;
+}
+
+/// x -> [x]
+struct Singleton;
+impl<T> Function<T> for Singleton {
+    type Apply = Cons<T, Nil>;
+}
+
 #[macro_export]
 macro_rules! func_call {
     ($name:ty[$param:ty]) => {
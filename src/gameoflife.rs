@@ -3,13 +3,15 @@
 use std::marker::PhantomData;
 
 use crate::{
-    booleans::{False, If, IfOutput, True, And, Or},
+    booleans::{False, LazyIf, LazyIfOutput, Ready, Thunk, True, And, Or},
     functions::{Function, Map, Predicate},
-    lists::{Cons, List, Nil, StrRepr, ListConcatAll},
+    lists::{Cons, List, Nil, StrRepr},
     m, make_list,
     numbers::{Negative, Number, PeanoAdd, Successor, Zero, N1, PeanoEqual, GEQZero, N3, N2},
-    typeif, logic,
-    functions::{Increment, Filter}, func_call, pred_call
+    pretty::Pretty,
+    json::{JsonValue, ToJson},
+    lazyif, logic,
+    functions::{ConcatAll, FilterWith, MapWith, Pipe}, func_call, pred_call
 };
 
 pub mod working_regular_impl {
@@ -76,9 +78,29 @@ impl<X: Number, Y: Number> StrRepr for Cell<X, Y> {
         format!("cell({}, {})", X::VALUE, Y::VALUE)
     }
 }
+impl<X: Number, Y: Number> Pretty for Cell<X, Y> {
+    fn pretty_at(_multiline: bool, _depth: usize) -> String {
+        format!("({}, {})", X::VALUE, Y::VALUE)
+    }
+}
+impl<X: Number, Y: Number> ToJson for Cell<X, Y> {
+    fn to_json() -> JsonValue {
+        JsonValue::point(X::VALUE, Y::VALUE)
+    }
+}
 
 #[derive(Default)]
 pub struct Delta<X: Number, Y: Number>(PhantomData<X>, PhantomData<Y>);
+impl<X: Number, Y: Number> Pretty for Delta<X, Y> {
+    fn pretty_at(_multiline: bool, _depth: usize) -> String {
+        format!("({}, {})", X::VALUE, Y::VALUE)
+    }
+}
+impl<X: Number, Y: Number> ToJson for Delta<X, Y> {
+    fn to_json() -> JsonValue {
+        JsonValue::point(X::VALUE, Y::VALUE)
+    }
+}
 type Neg1 = Negative<Successor<Zero>>;
 type Pos1 = Successor<Zero>;
 type NeighborlyDeltas = make_list!(
@@ -144,17 +166,26 @@ impl<K, Cmp> IncrementItemCounter<K, Cmp> for Nil {
 impl<K1, K2, N: GEQZero + Number, XS, Cmp> IncrementItemCounter<K1, Cmp> for Cons<(K2, N), XS>
 where
     Cmp: Predicate<(K1, K2)>,
-    XS: IncrementItemCounter<K1, Cmp>,
-    <If<<Cmp as Predicate<(K1, K2)>>::BoolApply, Cons<(K2, Successor<N>), XS>, Cons<(K2, N), <XS as IncrementItemCounter<K1, Cmp>>::Output>> as IfOutput>::Output: List,
-    If<<Cmp as Predicate<(K1, K2)>>::BoolApply, Cons<(K2, Successor<N>), XS>, Cons<(K2, N), <XS as IncrementItemCounter<K1, Cmp>>::Output>>: IfOutput
+    // the rest of the list is only searched if K2 is not the key
+    LazyIf<
+        <Cmp as Predicate<(K1, K2)>>::BoolApply,
+        Ready<Cons<(K2, Successor<N>), XS>>,
+        Thunk<KeepAndIncrementRest<K1, Cmp, (K2, N)>, XS>,
+    >: LazyIfOutput<Output: List>,
 {
-    type Output = typeif!(
+    type Output = lazyif!(
         pred_call!(Cmp[K1, K2]),
-        Cons<(K2, func_call!(Increment[N])), XS>,
-        Cons<(K2, N), <XS as IncrementItemCounter<K1, Cmp>>::Output>
+        Ready<Cons<(K2, Successor<N>), XS>>,
+        Thunk<KeepAndIncrementRest<K1, Cmp, (K2, N)>, XS>
     );
 }
 
+/// XS -> Cons<Kept, XS with the counter of K incremented>
+pub struct KeepAndIncrementRest<K, Cmp, Kept>(PhantomData<K>, PhantomData<Cmp>, PhantomData<Kept>);
+impl<K, Cmp, Kept, XS: IncrementItemCounter<K, Cmp>> Function<XS> for KeepAndIncrementRest<K, Cmp, Kept> {
+    type Apply = Cons<Kept, XS::Output>;
+}
+
 
 pub struct CountInstances;
 impl<AccList, Cmp> Function<(AccList, Nil, Cmp)> for CountInstances {
@@ -175,13 +206,13 @@ impl<Cmp, Target> Function<(Nil, Target)> for Contains<Cmp> {
 impl<X, XS, Cmp, Target> Function<(Cons<X, XS>, Target)> for Contains<Cmp>
 where
     Cmp: Predicate<(X, Target)>,
-    Self: Function<(XS, Target)>,
-    If<<Cmp as Predicate<(X, Target)>>::BoolApply, True, <Contains<Cmp> as Function<(XS, Target)>>::Apply>: IfOutput,
+    // the rest of the list is only searched if X is not the target
+    LazyIf<<Cmp as Predicate<(X, Target)>>::BoolApply, Ready<True>, Thunk<Self, (XS, Target)>>: LazyIfOutput,
  {
-    type Apply = typeif!(
+    type Apply = lazyif!(
         pred_call!(Cmp[X, Target]),
-        True,
-        func_call!(Self[(XS, Target)])
+        Ready<True>,
+        Thunk<Self, (XS, Target)>
     );
 }
 
@@ -212,23 +243,29 @@ impl<A, B> Function<(A, B)> for FirstTupleElement {
     type Apply = A;
 }
 
+/// Neighbor list -> list of (Cell, number of times it appears)
+pub struct CountCells;
+impl<Cells> Function<Cells> for CountCells
+where
+    CountInstances: Function<(Nil, Cells, CellComparisonFunction)>,
+{
+    type Apply = func_call!(CountInstances[Nil, Cells, CellComparisonFunction]);
+}
+
+type GOLStages<CurrentCells> = make_list!(
+    MapWith<GetCellNeighbors>,
+    ConcatAll,
+    CountCells,
+    FilterWith<CellShouldLive<CurrentCells>>,
+    MapWith<FirstTupleElement>,
+);
+
 pub struct SingleGOLIter;
-impl<CurrentCells> Function<CurrentCells> for SingleGOLIter 
-where 
-    CurrentCells: Map<GetCellNeighbors>,
-    <CurrentCells as Map<GetCellNeighbors>>::Output: ListConcatAll,
-    CountInstances: Function<(Nil, <<CurrentCells as Map<GetCellNeighbors>>::Output as ListConcatAll>::ListConcatAll, CellComparisonFunction)>,
-    <CountInstances as Function<(Nil, <<CurrentCells as Map<GetCellNeighbors>>::Output as ListConcatAll>::ListConcatAll, CellComparisonFunction)>>::Apply: Filter<CellShouldLive<CurrentCells>>,
-    <<CountInstances as Function<(Nil, <<CurrentCells as Map<GetCellNeighbors>>::Output as ListConcatAll>::ListConcatAll, CellComparisonFunction)>>::Apply as Filter<CellShouldLive<CurrentCells>>>::Output: Map<FirstTupleElement>
+impl<CurrentCells> Function<CurrentCells> for SingleGOLIter
+where
+    Pipe<GOLStages<CurrentCells>>: Function<CurrentCells>,
 {
-    type Apply = <<
-        func_call!(CountInstances[
-            Nil,
-            <<CurrentCells as Map<GetCellNeighbors>>::Output as ListConcatAll>::ListConcatAll,
-            CellComparisonFunction
-        ])
-        as Filter<CellShouldLive<CurrentCells>>>::Output 
-        as Map<FirstTupleElement>>::Output;
+    type Apply = <Pipe<GOLStages<CurrentCells>> as Function<CurrentCells>>::Apply;
 }
 
 pub trait Reify {
@@ -244,23 +281,35 @@ impl<X: Number, Y: Number> Reify for Cell<X, Y> {
     }
 }
 
-impl<X: Reify> Reify for Cons<X, Nil> {
+impl<X: Reify, XS: ReifyList<X::RuntimeValue>> Reify for Cons<X, XS> {
     type RuntimeValue = Vec<X::RuntimeValue>;
+
     fn reify() -> Self::RuntimeValue {
-        vec![X::reify()]
+        XS::reify_into(vec![X::reify()])
     }
 }
 
-impl<X: Reify, XS: Reify<RuntimeValue = Vec<X::RuntimeValue>>> Reify for Cons<X, XS>
-{
-    type RuntimeValue = Vec<X::RuntimeValue>;
+/// Reify the items of a list whose items reify to `T`. Unlike `Reify` this is
+/// also implemented for `Nil`, since the item type is given from the outside
+pub trait ReifyList<T> {
+    fn reify_into(prefix: Vec<T>) -> Vec<T>;
 
-    fn reify() -> Self::RuntimeValue {
-        let mut ret = vec![X::reify()];
-        ret.extend(XS::reify());
-        ret
+    fn reify_list() -> Vec<T> {
+        Self::reify_into(Vec::new())
+    }
+}
+
+impl<T> ReifyList<T> for Nil {
+    fn reify_into(prefix: Vec<T>) -> Vec<T> {
+        prefix
+    }
+}
+
+impl<T, X: Reify<RuntimeValue = T>, XS: ReifyList<T>> ReifyList<T> for Cons<X, XS> {
+    fn reify_into(mut prefix: Vec<T>) -> Vec<T> {
+        prefix.push(X::reify());
+        XS::reify_into(prefix)
     }
-    
 }
 
 #[cfg(test)]
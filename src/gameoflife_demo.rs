@@ -1,4 +1,5 @@
 #![feature(generic_associated_types, associated_type_bounds)]
+// stepping the glider nests deeper than the default limit of 128
 #![recursion_limit = "1024"]
 
 use numbers::{Successor, N5};
@@ -11,6 +12,20 @@ mod numbers;
 mod lists;
 mod functions;
 mod gameoflife;
+mod pretty;
+mod json;
+mod hlist;
+mod vect;
+mod units;
+mod matrix;
+mod statemachine;
+mod symmetry;
+mod sudoku;
+mod sat;
+mod combinatorics;
+mod option;
+mod result;
+mod treemap;
 
 type N10 = m!(add N5, N5);
 type N11 = Successor<N10>;
@@ -0,0 +1,248 @@
+//! heterogeneous lists that carry runtime values
+//!
+//! `Cons`/`Nil` only exist at the type level. `HCons`/`HNil` have the same shape but store
+//! a value for every element, and every `HList` knows the `Cons`/`Nil` list of its element
+//! types, so the type-level machinery (`Length`, `Map`) decides the types of the
+//! value-level operations.
+
+use crate::{
+    functions::{Function, Map},
+    lists::{Cons, Length, List, Nil},
+    numbers::{GEQZero, Number, Successor, Zero},
+};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct HNil;
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct HCons<H, T>(pub H, pub T);
+
+/// Build an `HList` value
+#[macro_export]
+macro_rules! hlist {
+    ($x:expr, $($xs:expr),+ $(,)?) => {
+        $crate::hlist::HCons($x, $crate::hlist!($($xs,)+))
+    };
+    ($x:expr $(,)?) => {
+        $crate::hlist::HCons($x, $crate::hlist::HNil)
+    };
+    () => {
+        $crate::hlist::HNil
+    };
+}
+
+pub trait HList: Sized {
+    /// the type-level list of element types
+    type Types: List + Length;
+
+    const LEN: usize = <<Self::Types as Length>::Length as Number>::VALUE as usize;
+
+    fn len(&self) -> usize {
+        Self::LEN
+    }
+
+    fn is_empty(&self) -> bool {
+        Self::LEN == 0
+    }
+
+    fn prepend<X>(self, x: X) -> HCons<X, Self> {
+        HCons(x, self)
+    }
+}
+impl HList for HNil {
+    type Types = Nil;
+}
+impl<H, T: HList> HList for HCons<H, T> {
+    type Types = Cons<H, T::Types>;
+}
+
+/// Value-level counterpart of a type-level `Function`: computes a value of type `Self::Apply`
+pub trait Call<T>: Function<T> {
+    fn call(&self, arg: T) -> Self::Apply;
+}
+
+/// Apply `F` to every element. The resulting element types are `<Self::Types as Map<F>>::Output`
+pub trait HMap<F>: HList<Types: Map<F>> {
+    type Output: HList<Types = <Self::Types as Map<F>>::Output>;
+    fn map(self, f: &F) -> Self::Output;
+}
+impl<F> HMap<F> for HNil {
+    type Output = HNil;
+    fn map(self, _f: &F) -> HNil {
+        HNil
+    }
+}
+impl<F: Call<H>, H, T: HMap<F>> HMap<F> for HCons<H, T> {
+    type Output = HCons<F::Apply, T::Output>;
+    fn map(self, f: &F) -> Self::Output {
+        HCons(f.call(self.0), self.1.map(f))
+    }
+}
+
+/// Left fold: `F` is called with `(accumulator, element)` and returns the next accumulator.
+/// The accumulator may change type at every step
+pub trait HFold<F, Acc>: HList {
+    type Output;
+    fn fold(self, acc: Acc, f: &F) -> Self::Output;
+}
+impl<F, Acc> HFold<F, Acc> for HNil {
+    type Output = Acc;
+    fn fold(self, acc: Acc, _f: &F) -> Acc {
+        acc
+    }
+}
+impl<F: Call<(Acc, H)>, Acc, H, T: HFold<F, F::Apply>> HFold<F, Acc> for HCons<H, T> {
+    type Output = T::Output;
+    fn fold(self, acc: Acc, f: &F) -> Self::Output {
+        let acc = f.call((acc, self.0));
+        self.1.fold(acc, f)
+    }
+}
+
+/// Element at the type-level index `N`
+pub trait HGet<N: Number> {
+    type Output;
+    fn get(&self) -> &Self::Output;
+    fn get_mut(&mut self) -> &mut Self::Output;
+}
+impl<H, T> HGet<Zero> for HCons<H, T> {
+    type Output = H;
+    fn get(&self) -> &H {
+        &self.0
+    }
+    fn get_mut(&mut self) -> &mut H {
+        &mut self.0
+    }
+}
+impl<N: Number + GEQZero, H, T: HGet<N>> HGet<Successor<N>> for HCons<H, T> {
+    type Output = T::Output;
+    fn get(&self) -> &Self::Output {
+        self.1.get()
+    }
+    fn get_mut(&mut self) -> &mut Self::Output {
+        self.1.get_mut()
+    }
+}
+
+pub trait IntoTuple {
+    type Tuple;
+    fn into_tuple(self) -> Self::Tuple;
+}
+impl IntoTuple for HNil {
+    type Tuple = ();
+    fn into_tuple(self) {}
+}
+
+macro_rules! hlist_impl_into_tuple {
+    ($($t:ident,)*) => {
+        impl<$($t,)*> IntoTuple for $crate::make_hlist_type!($($t,)*) {
+            type Tuple = ($($t,)*);
+            #[allow(non_snake_case)]
+            fn into_tuple(self) -> Self::Tuple {
+                let hlist_pat!($($t,)*) = self;
+                ($($t,)*)
+            }
+        }
+    };
+}
+
+/// `HCons<A, HCons<B, HNil>>` from `A, B`
+#[macro_export]
+macro_rules! make_hlist_type {
+    ($x:ty, $($xs:ty),+ $(,)?) => {
+        $crate::hlist::HCons<$x, $crate::make_hlist_type!($($xs,)+)>
+    };
+    ($x:ty $(,)?) => {
+        $crate::hlist::HCons<$x, $crate::hlist::HNil>
+    };
+    () => {
+        $crate::hlist::HNil
+    };
+}
+
+macro_rules! hlist_pat {
+    ($x:ident, $($xs:ident,)+) => {
+        HCons($x, hlist_pat!($($xs,)+))
+    };
+    ($x:ident,) => {
+        HCons($x, HNil)
+    };
+}
+
+hlist_impl_into_tuple!(T1,);
+hlist_impl_into_tuple!(T1,T2,);
+hlist_impl_into_tuple!(T1,T2,T3,);
+hlist_impl_into_tuple!(T1,T2,T3,T4,);
+hlist_impl_into_tuple!(T1,T2,T3,T4,T5,);
+hlist_impl_into_tuple!(T1,T2,T3,T4,T5,T6,);
+hlist_impl_into_tuple!(T1,T2,T3,T4,T5,T6,T7,);
+hlist_impl_into_tuple!(T1,T2,T3,T4,T5,T6,T7,T8,);
+hlist_impl_into_tuple!(T1,T2,T3,T4,T5,T6,T7,T8,T9,);
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        functions::Function,
+        hlist::{Call, HFold, HGet, HList, HMap, IntoTuple},
+        numbers::{N0, N1, N2},
+    };
+
+    /// `i32 -> i64`, `&str -> usize`, `bool -> bool`
+    struct Widen;
+    impl Function<i32> for Widen {
+        type Apply = i64;
+    }
+    impl Call<i32> for Widen {
+        fn call(&self, arg: i32) -> i64 {
+            arg as i64 * 2
+        }
+    }
+    impl Function<&str> for Widen {
+        type Apply = usize;
+    }
+    impl Call<&str> for Widen {
+        fn call(&self, arg: &str) -> usize {
+            arg.len()
+        }
+    }
+    impl Function<bool> for Widen {
+        type Apply = bool;
+    }
+    impl Call<bool> for Widen {
+        fn call(&self, arg: bool) -> bool {
+            !arg
+        }
+    }
+
+    /// appends the debug representation of every element to a string
+    struct Describe;
+    impl<T: std::fmt::Debug> Function<(String, T)> for Describe {
+        type Apply = String;
+    }
+    impl<T: std::fmt::Debug> Call<(String, T)> for Describe {
+        fn call(&self, (acc, x): (String, T)) -> String {
+            format!("{}{:?};", acc, x)
+        }
+    }
+
+    #[test]
+    fn test_len_and_get() {
+        let mut list = hlist![1i32, "four", true];
+        assert_eq!(list.len(), 3);
+        assert_eq!(<make_hlist_type!()>::LEN, 0);
+
+        assert_eq!(*HGet::<N0>::get(&list), 1);
+        assert_eq!(*HGet::<N1>::get(&list), "four");
+        *HGet::<N2>::get_mut(&mut list) = false;
+        assert_eq!(list.into_tuple(), (1, "four", false));
+    }
+
+    #[test]
+    fn test_map_and_fold() {
+        let list = hlist![21i32, "four", true];
+        let mapped = list.map(&Widen);
+        assert_eq!(mapped, hlist![42i64, 4usize, false]);
+
+        let described = mapped.fold(String::new(), &Describe);
+        assert_eq!(described, "42;4;false;");
+    }
+}
@@ -0,0 +1,464 @@
+//! JSON export of type-level values, plus a small runtime parser so the output can be read back
+
+use std::fmt;
+
+use crate::{
+    booleans::{Bool, False, True},
+    lists::{Cons, Nil},
+    numbers::Number,
+};
+
+/// runtime representation of a JSON document
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    /// keys are kept in insertion order
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    /// object with integer `x` and `y` fields, used for queens, cells and deltas
+    pub fn point(x: isize, y: isize) -> JsonValue {
+        JsonValue::Object(vec![
+            ("x".into(), JsonValue::Number(x as f64)),
+            ("y".into(), JsonValue::Number(y as f64)),
+        ])
+    }
+}
+
+fn write_json_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            '\u{08}' => f.write_str("\\b")?,
+            '\u{0c}' => f.write_str("\\f")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    f.write_str("\"")
+}
+
+impl fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonValue::Null => f.write_str("null"),
+            JsonValue::Bool(b) => write!(f, "{}", b),
+            JsonValue::Number(n) => write!(f, "{}", n),
+            JsonValue::String(s) => write_json_string(f, s),
+            JsonValue::Array(items) => {
+                f.write_str("[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                f.write_str("]")
+            }
+            JsonValue::Object(fields) => {
+                f.write_str("{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write_json_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+pub trait ToJson {
+    fn to_json() -> JsonValue;
+
+    fn json_string() -> String {
+        Self::to_json().to_string()
+    }
+}
+
+impl<N: Number> ToJson for N {
+    fn to_json() -> JsonValue {
+        JsonValue::Number(N::VALUE as f64)
+    }
+}
+
+impl ToJson for True {
+    fn to_json() -> JsonValue {
+        JsonValue::Bool(Self::VALUE)
+    }
+}
+impl ToJson for False {
+    fn to_json() -> JsonValue {
+        JsonValue::Bool(Self::VALUE)
+    }
+}
+
+trait JsonItems {
+    fn json_items() -> Vec<JsonValue>;
+}
+impl JsonItems for Nil {
+    fn json_items() -> Vec<JsonValue> {
+        vec![]
+    }
+}
+impl<Item: ToJson, Rest: JsonItems> JsonItems for Cons<Item, Rest> {
+    fn json_items() -> Vec<JsonValue> {
+        let mut rest = Rest::json_items();
+        rest.insert(0, Item::to_json());
+        rest
+    }
+}
+
+impl ToJson for Nil {
+    fn to_json() -> JsonValue {
+        JsonValue::Array(vec![])
+    }
+}
+impl<I, R> ToJson for Cons<I, R>
+where
+    Cons<I, R>: JsonItems,
+{
+    fn to_json() -> JsonValue {
+        JsonValue::Array(Self::json_items())
+    }
+}
+
+/// tuples become JSON arrays
+macro_rules! tuple_impl_to_json {
+    ($($t:ident,)*) => {
+        impl<$($t,)*> ToJson for ($($t,)*) where
+            $(
+                $t: ToJson,
+            )*
+        {
+            fn to_json() -> JsonValue {
+                JsonValue::Array(vec![$(<$t as ToJson>::to_json(),)*])
+            }
+        }
+    };
+}
+
+tuple_impl_to_json!(T1,);
+tuple_impl_to_json!(T1,T2,);
+tuple_impl_to_json!(T1,T2,T3,);
+tuple_impl_to_json!(T1,T2,T3,T4,);
+tuple_impl_to_json!(T1,T2,T3,T4,T5,);
+tuple_impl_to_json!(T1,T2,T3,T4,T5,T6,);
+tuple_impl_to_json!(T1,T2,T3,T4,T5,T6,T7,);
+tuple_impl_to_json!(T1,T2,T3,T4,T5,T6,T7,T8,);
+tuple_impl_to_json!(T1,T2,T3,T4,T5,T6,T7,T8,T9,);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonParseError {
+    /// byte offset into the input where parsing failed
+    pub position: usize,
+    pub message: &'static str,
+}
+
+impl fmt::Display for JsonParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at byte {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for JsonParseError {}
+
+/// Parse a complete JSON document
+pub fn parse_json(input: &str) -> Result<JsonValue, JsonParseError> {
+    let mut parser = Parser { input: input.as_bytes(), pos: 0 };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.pos != parser.input.len() {
+        return Err(parser.error("trailing characters"));
+    }
+    Ok(value)
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: &'static str) -> JsonParseError {
+        JsonParseError { position: self.pos, message }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8, message: &'static str) -> Result<(), JsonParseError> {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(message))
+        }
+    }
+
+    fn literal(&mut self, text: &str, value: JsonValue) -> Result<JsonValue, JsonParseError> {
+        if self.input[self.pos..].starts_with(text.as_bytes()) {
+            self.pos += text.len();
+            Ok(value)
+        } else {
+            Err(self.error("invalid literal"))
+        }
+    }
+
+    fn value(&mut self) -> Result<JsonValue, JsonParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            None => Err(self.error("unexpected end of input")),
+            Some(b'n') => self.literal("null", JsonValue::Null),
+            Some(b't') => self.literal("true", JsonValue::Bool(true)),
+            Some(b'f') => self.literal("false", JsonValue::Bool(false)),
+            Some(b'"') => Ok(JsonValue::String(self.string()?)),
+            Some(b'[') => self.array(),
+            Some(b'{') => self.object(),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) => Err(self.error("unexpected character")),
+        }
+    }
+
+    fn array(&mut self) -> Result<JsonValue, JsonParseError> {
+        self.expect(b'[', "expected '['")?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(JsonValue::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(JsonValue::Array(items));
+                }
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<JsonValue, JsonParseError> {
+        self.expect(b'{', "expected '{'")?;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(JsonValue::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(b':', "expected ':'")?;
+            fields.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(JsonValue::Object(fields));
+                }
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<JsonValue, JsonParseError> {
+        let start = self.pos;
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        match self.peek() {
+            Some(b'0') => self.pos += 1,
+            Some(b'1'..=b'9') => self.digits(),
+            _ => return Err(self.error("expected digit")),
+        }
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            if !matches!(self.peek(), Some(b'0'..=b'9')) {
+                return Err(self.error("expected digit after '.'"));
+            }
+            self.digits();
+        }
+        if let Some(b'e' | b'E') = self.peek() {
+            self.pos += 1;
+            if let Some(b'+' | b'-') = self.peek() {
+                self.pos += 1;
+            }
+            if !matches!(self.peek(), Some(b'0'..=b'9')) {
+                return Err(self.error("expected digit in exponent"));
+            }
+            self.digits();
+        }
+        // only ASCII was consumed, so this slice is valid UTF-8
+        let text = std::str::from_utf8(&self.input[start..self.pos]).unwrap();
+        text.parse()
+            .map(JsonValue::Number)
+            .map_err(|_| JsonParseError { position: start, message: "invalid number" })
+    }
+
+    fn digits(&mut self) {
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, JsonParseError> {
+        let digits = self.input.get(self.pos..self.pos + 4).ok_or_else(|| self.error("truncated \\u escape"))?;
+        let text = std::str::from_utf8(digits).map_err(|_| self.error("invalid \\u escape"))?;
+        let code = u32::from_str_radix(text, 16).map_err(|_| self.error("invalid \\u escape"))?;
+        self.pos += 4;
+        Ok(code)
+    }
+
+    fn string(&mut self) -> Result<String, JsonParseError> {
+        self.expect(b'"', "expected string")?;
+        let mut bytes = Vec::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error("unterminated string")),
+                Some(b'"') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    let escaped = match self.peek() {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{08}',
+                        Some(b'f') => '\u{0c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => {
+                            self.pos += 1;
+                            let mut code = self.hex4()?;
+                            if (0xd800..0xdc00).contains(&code) {
+                                // high surrogate, must be followed by an escaped low surrogate
+                                if !self.input[self.pos..].starts_with(b"\\u") {
+                                    return Err(self.error("unpaired surrogate"));
+                                }
+                                self.pos += 2;
+                                let low = self.hex4()?;
+                                if !(0xdc00..0xe000).contains(&low) {
+                                    return Err(self.error("unpaired surrogate"));
+                                }
+                                code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                            }
+                            let c = char::from_u32(code).ok_or_else(|| self.error("invalid \\u escape"))?;
+                            let mut buf = [0; 4];
+                            bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                            continue;
+                        }
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    self.pos += 1;
+                    let mut buf = [0; 4];
+                    bytes.extend_from_slice(escaped.encode_utf8(&mut buf).as_bytes());
+                }
+                Some(c) if c < 0x20 => return Err(self.error("control character in string")),
+                Some(c) => {
+                    bytes.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+        // the input was a &str and we only split it at ASCII boundaries
+        Ok(String::from_utf8(bytes).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        booleans::{False, True},
+        functions::Function,
+        gameoflife::{Cell, Delta},
+        json::{parse_json, JsonValue, ToJson},
+        make_list,
+        nqueens::{NQueensSolution, Queen},
+        numbers::{Negative, N0, N1, N2, N4},
+    };
+
+    fn round_trip<T: ToJson>() -> JsonValue {
+        let value = T::to_json();
+        let parsed = parse_json(&T::json_string()).unwrap();
+        assert_eq!(parsed, value);
+        parsed
+    }
+
+    #[test]
+    fn test_scalars() {
+        assert_eq!(N2::json_string(), "2");
+        assert_eq!(<Negative<N1>>::json_string(), "-1");
+        assert_eq!(True::json_string(), "true");
+        assert_eq!(False::json_string(), "false");
+        assert_eq!(<(N1, True)>::json_string(), "[1,true]");
+        assert_eq!(<Queen<N0, N1>>::json_string(), r#"{"x":0,"y":1}"#);
+        assert_eq!(<Cell<Negative<N1>, N2>>::json_string(), r#"{"x":-1,"y":2}"#);
+        assert_eq!(<Delta<N0, Negative<N1>>>::json_string(), r#"{"x":0,"y":-1}"#);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        round_trip::<N4>();
+        round_trip::<False>();
+        round_trip::<make_list!()>();
+        round_trip::<make_list!((N1, True), (N2, False))>();
+
+        type Solutions = <NQueensSolution as Function<N4>>::Apply;
+        let parsed = round_trip::<Solutions>();
+        match parsed {
+            JsonValue::Array(solutions) => assert_eq!(solutions.len(), 2),
+            _ => panic!("expected an array of solutions"),
+        }
+    }
+
+    #[test]
+    fn test_parser() {
+        let parsed = parse_json(r#" { "a" : [1, -2.5e1, null], "b\né😀" : "x\"y" } "#).unwrap();
+        assert_eq!(
+            parsed,
+            JsonValue::Object(vec![
+                (
+                    "a".into(),
+                    JsonValue::Array(vec![JsonValue::Number(1.0), JsonValue::Number(-25.0), JsonValue::Null])
+                ),
+                ("b\né😀".into(), JsonValue::String("x\"y".into())),
+            ])
+        );
+        assert_eq!(parse_json(&parsed.to_string()).unwrap(), parsed);
+        assert_eq!(parse_json(r#""\ud83d\ude00\t""#).unwrap(), JsonValue::String("😀\t".into()));
+
+        assert!(parse_json("[1,]").is_err());
+        assert!(parse_json("01").is_err());
+        assert!(parse_json("\"abc").is_err());
+        assert!(parse_json("{} x").is_err());
+    }
+}
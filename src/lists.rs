@@ -1,5 +1,10 @@
 use std::marker::PhantomData;
 
+use crate::{
+    numbers::{GEQZero, Number, Successor, Zero},
+    result::{Err, Ok, TypeResult},
+};
+
 #[derive(Default)]
 pub struct Nil;
 #[derive(Default)]
@@ -18,6 +23,11 @@ macro_rules! make_list {
     };
 }
 
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a type-level List",
+    label = "expected a List",
+    note = "lists are built from `Nil` and `Cons<X, XS>` (see `make_list!`)"
+)]
 pub trait List {}
 impl List for Nil {}
 impl<Item, Rest> List for Cons<Item, Rest> where Rest: List {}
@@ -92,14 +102,137 @@ tuple_impl_str_repr!(T1,T2,T3,T4,T5,T6,T7,);
 tuple_impl_str_repr!(T1,T2,T3,T4,T5,T6,T7,T8,);
 tuple_impl_str_repr!(T1,T2,T3,T4,T5,T6,T7,T8,T9,);
 
+/// Number of items in a list
+pub trait Length: List {
+    type Length: Number + GEQZero;
+}
+impl Length for Nil {
+    type Length = Zero;
+}
+impl<Item, Rest: Length> Length for Cons<Item, Rest> {
+    type Length = Successor<Rest::Length>;
+}
+
+/// The list in reverse order
+pub trait Reverse: List {
+    type Output: List + ListConcat;
+}
+impl<L: List + ReverseOnto<Nil>> Reverse for L {
+    type Output = <L as ReverseOnto<Nil>>::Output;
+}
+
+/// Reverse the list and put it in front of `Acc`
+pub trait ReverseOnto<Acc: List> {
+    type Output: List + ListConcat;
+}
+impl<Acc: List + ListConcat> ReverseOnto<Acc> for Nil {
+    type Output = Acc;
+}
+impl<Acc: List, X, XS: ReverseOnto<Cons<X, Acc>>> ReverseOnto<Acc> for Cons<X, XS> {
+    type Output = <XS as ReverseOnto<Cons<X, Acc>>>::Output;
+}
+
+/// Pair up the items of two lists of the same length
+#[diagnostic::on_unimplemented(
+    message = "cannot zip `{Self}` with `{Other}`",
+    label = "the lists must have the same length",
+    note = "use `ZipShortest` to drop the extra items of the longer list"
+)]
+pub trait Zip<Other> {
+    type Output: List;
+}
+impl Zip<Nil> for Nil {
+    type Output = Nil;
+}
+impl<X, XS: Zip<YS>, Y, YS> Zip<Cons<Y, YS>> for Cons<X, XS> {
+    type Output = Cons<(X, Y), XS::Output>;
+}
+
+/// `Zip` that stops at the end of the shorter list
+pub trait ZipShortest<Other> {
+    type Output: List;
+}
+impl<Other> ZipShortest<Other> for Nil {
+    type Output = Nil;
+}
+impl<X, XS> ZipShortest<Nil> for Cons<X, XS> {
+    type Output = Nil;
+}
+impl<X, XS: ZipShortest<YS>, Y, YS> ZipShortest<Cons<Y, YS>> for Cons<X, XS> {
+    type Output = Cons<(X, Y), XS::Output>;
+}
+
+/// Split a list of pairs into the list of first items and the list of second items
+pub trait Unzip {
+    type Left: List;
+    type Right: List;
+}
+impl Unzip for Nil {
+    type Left = Nil;
+    type Right = Nil;
+}
+impl<A, B, Rest: Unzip> Unzip for Cons<(A, B), Rest> {
+    type Left = Cons<A, Rest::Left>;
+    type Right = Cons<B, Rest::Right>;
+}
+
+/// Pair every item with its index, counting from 0
+pub trait Enumerate {
+    type Output: List;
+}
+impl<L: EnumerateFrom<Zero>> Enumerate for L {
+    type Output = <L as EnumerateFrom<Zero>>::Output;
+}
+
+/// Pair every item with its index, counting from `Start`
+pub trait EnumerateFrom<Start> {
+    type Output: List;
+}
+impl<Start> EnumerateFrom<Start> for Nil {
+    type Output = Nil;
+}
+impl<Start: Number + GEQZero, X, XS: EnumerateFrom<Successor<Start>>> EnumerateFrom<Start> for Cons<X, XS> {
+    type Output = Cons<(Start, X), XS::Output>;
+}
+
+fn zip_type_test() {
+    use crate::numbers::{N0, N1, N2, N3};
+
+    type Left = make_list!(N1, N2);
+    type Right = make_list!(N3, N0);
+    let _: <Left as Zip<Right>>::Output = <make_list!((N1, N3), (N2, N0))>::default();
+    let _: <Nil as Zip<Nil>>::Output = Nil;
+    type Longer = make_list!(N1, N2, N3);
+    let _: <Longer as ZipShortest<Right>>::Output = <make_list!((N1, N3), (N2, N0))>::default();
+    let _: <Left as ZipShortest<Nil>>::Output = Nil;
+
+    type Pairs = <Left as Zip<Right>>::Output;
+    let _: <Pairs as Unzip>::Left = Left::default();
+    let _: <Pairs as Unzip>::Right = Right::default();
+
+    type Threes = make_list!(N3, N3, N3);
+    let _: <Threes as Enumerate>::Output = <make_list!((N0, N3), (N1, N3), (N2, N3))>::default();
+    let _: <make_list!(N3) as EnumerateFrom<N2>>::Output = <make_list!((N2, N3))>::default();
+}
+
+/// Error tag of `First` on `Nil`
+#[derive(Default)]
+pub struct EmptyList;
+impl StrRepr for EmptyList {
+    fn str_repr() -> String {
+        "First of an empty list".into()
+    }
+}
+
+/// `Ok` of the first item, or `Err<EmptyList>`
 pub trait First {
-    type First;
+    type First: TypeResult;
 }
 impl First for Nil {
-    type First = Nil;
+    type First = Err<EmptyList>;
 }
 impl<ItemType, Rest> First for Cons<ItemType, Rest> {
-    type First = ItemType;
+    type First = Ok<ItemType>;
 }
 
 pub trait ListConcat: List {
@@ -1,4 +1,6 @@
 #![feature(generic_associated_types, associated_type_bounds)]
+// only the 9x9 Sudoku fixture nests deeper than the default limit of 128
+#![cfg_attr(feature = "slow-type-tests", recursion_limit = "1024")]
 
 use functions::Function;
 use nqueens::NQueensSolution;
@@ -10,6 +12,20 @@ mod numbers;
 mod lists;
 mod functions;
 mod gameoflife;
+mod pretty;
+mod json;
+mod hlist;
+mod vect;
+mod units;
+mod matrix;
+mod statemachine;
+mod symmetry;
+mod sudoku;
+mod sat;
+mod combinatorics;
+mod option;
+mod result;
+mod treemap;
 
 use lists::StrRepr;
 
@@ -0,0 +1,179 @@
+//! matrices whose shape is made of type-level `Number`s, so shape mismatches are compile errors
+
+use std::{
+    fmt,
+    marker::PhantomData,
+    ops::{Add, Mul},
+};
+
+use crate::{
+    booleans::True,
+    numbers::{GEQZero, LiftNumber, Number, NumberWrapper, PeanoEqual, PeanoLT},
+};
+
+/// A `Rows` x `Cols` matrix, stored row-major. Negative shapes do not compile
+pub struct Matrix<T, Rows: Number + GEQZero, Cols: Number + GEQZero>(Vec<T>, PhantomData<Rows>, PhantomData<Cols>);
+
+impl<T, Rows: Number + GEQZero, Cols: Number + GEQZero> Matrix<T, Rows, Cols> {
+    pub const ROWS: usize = Rows::VALUE as usize;
+    pub const COLS: usize = Cols::VALUE as usize;
+
+    /// Build a matrix by calling `f(row, col)` for every entry
+    pub fn from_fn<F: FnMut(usize, usize) -> T>(mut f: F) -> Self {
+        let mut items = Vec::with_capacity(Self::ROWS * Self::COLS);
+        for row in 0..Self::ROWS {
+            for col in 0..Self::COLS {
+                items.push(f(row, col));
+            }
+        }
+        Matrix(items, PhantomData, PhantomData)
+    }
+
+    /// Build a matrix from an array of rows. The array sizes have to match the shape
+    pub fn from_rows<const R: usize, const C: usize>(rows: [[T; C]; R]) -> Self
+    where
+        NumberWrapper<R>: LiftNumber<Value = Rows>,
+        NumberWrapper<C>: LiftNumber<Value = Cols>,
+    {
+        Matrix(IntoIterator::into_iter(rows).flat_map(IntoIterator::into_iter).collect(), PhantomData, PhantomData)
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (Self::ROWS, Self::COLS)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.0[row * Self::COLS..(row + 1) * Self::COLS]
+    }
+
+    /// Entry at a type-level position, which is checked to be in bounds and not negative
+    pub fn get<Row, Col>(&self) -> &T
+    where
+        Row: Number + GEQZero + PeanoLT<Rows, LT = True>,
+        Col: Number + GEQZero + PeanoLT<Cols, LT = True>,
+    {
+        &self[(Row::VALUE as usize, Col::VALUE as usize)]
+    }
+
+    pub fn get_mut<Row, Col>(&mut self) -> &mut T
+    where
+        Row: Number + GEQZero + PeanoLT<Rows, LT = True>,
+        Col: Number + GEQZero + PeanoLT<Cols, LT = True>,
+    {
+        &mut self.0[Row::VALUE as usize * Self::COLS + Col::VALUE as usize]
+    }
+
+    pub fn transpose(&self) -> Matrix<T, Cols, Rows>
+    where
+        T: Clone,
+    {
+        Matrix::from_fn(|row, col| self[(col, row)].clone())
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Matrix<U, Rows, Cols> {
+        Matrix(self.0.iter().map(f).collect(), PhantomData, PhantomData)
+    }
+}
+
+impl<T: From<u8>, N: Number + GEQZero> Matrix<T, N, N> {
+    /// Only square matrices have an identity
+    pub fn identity() -> Self {
+        Self::from_fn(|row, col| T::from((row == col) as u8))
+    }
+}
+
+impl<T, Rows: Number + GEQZero, Cols: Number + GEQZero> std::ops::Index<(usize, usize)> for Matrix<T, Rows, Cols> {
+    type Output = T;
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert!(row < Self::ROWS && col < Self::COLS, "matrix index out of bounds");
+        &self.0[row * Self::COLS + col]
+    }
+}
+
+impl<T, Rows: Number + GEQZero, Cols: Number + GEQZero> std::ops::IndexMut<(usize, usize)> for Matrix<T, Rows, Cols> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        assert!(row < Self::ROWS && col < Self::COLS, "matrix index out of bounds");
+        &mut self.0[row * Self::COLS + col]
+    }
+}
+
+/// `(R x C) * (K x C2)` only compiles when `C == K`
+impl<T, R: Number + GEQZero, C: Number + GEQZero, K: Number + GEQZero, C2: Number + GEQZero> Mul<&Matrix<T, K, C2>> for &Matrix<T, R, C>
+where
+    T: Copy + From<u8> + Add<Output = T> + Mul<Output = T>,
+    C: PeanoEqual<K, Equal = True>,
+{
+    type Output = Matrix<T, R, C2>;
+    fn mul(self, other: &Matrix<T, K, C2>) -> Self::Output {
+        Matrix::from_fn(|row, col| {
+            (0..Matrix::<T, R, C>::COLS).fold(T::from(0), |acc, i| acc + self[(row, i)] * other[(i, col)])
+        })
+    }
+}
+
+impl<T, R: Number + GEQZero, C: Number + GEQZero, K: Number + GEQZero, C2: Number + GEQZero> Mul<Matrix<T, K, C2>> for Matrix<T, R, C>
+where
+    T: Copy + From<u8> + Add<Output = T> + Mul<Output = T>,
+    C: PeanoEqual<K, Equal = True>,
+{
+    type Output = Matrix<T, R, C2>;
+    fn mul(self, other: Matrix<T, K, C2>) -> Self::Output {
+        &self * &other
+    }
+}
+
+impl<T: Copy + Add<Output = T>, Rows: Number + GEQZero, Cols: Number + GEQZero> Add for Matrix<T, Rows, Cols> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Matrix(self.0.iter().zip(other.0.iter()).map(|(&a, &b)| a + b).collect(), PhantomData, PhantomData)
+    }
+}
+
+impl<T: Clone, Rows: Number + GEQZero, Cols: Number + GEQZero> Clone for Matrix<T, Rows, Cols> {
+    fn clone(&self) -> Self {
+        Matrix(self.0.clone(), PhantomData, PhantomData)
+    }
+}
+
+impl<T: PartialEq, Rows: Number + GEQZero, Cols: Number + GEQZero> PartialEq for Matrix<T, Rows, Cols> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: fmt::Debug, Rows: Number + GEQZero, Cols: Number + GEQZero> fmt::Debug for Matrix<T, Rows, Cols> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries((0..Self::ROWS).map(|row| self.row(row))).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        matrix::Matrix,
+        numbers::{N0, N1, N2, N3},
+    };
+
+    #[test]
+    fn test_mul() {
+        let a: Matrix<i32, N2, N3> = Matrix::from_rows([[1, 2, 3], [4, 5, 6]]);
+        let b: Matrix<i32, N3, N2> = Matrix::from_rows([[7, 8], [9, 10], [11, 12]]);
+        let product: Matrix<i32, N2, N2> = &a * &b;
+        assert_eq!(product, Matrix::from_rows([[58, 64], [139, 154]]));
+
+        assert_eq!(product.clone() * Matrix::identity(), product);
+        assert_eq!(Matrix::<i32, N3, N3>::identity() * b.clone(), b);
+    }
+
+    #[test]
+    fn test_transpose_and_get() {
+        let mut a: Matrix<i32, N2, N3> = Matrix::from_rows([[1, 2, 3], [4, 5, 6]]);
+        let t: Matrix<i32, N3, N2> = a.transpose();
+        assert_eq!(t, Matrix::from_rows([[1, 4], [2, 5], [3, 6]]));
+        assert_eq!(t.shape(), (3, 2));
+
+        assert_eq!(*a.get::<N1, N2>(), 6);
+        *a.get_mut::<N0, N0>() = 10;
+        assert_eq!(a[(0, 0)], 10);
+    }
+}
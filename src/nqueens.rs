@@ -1,20 +1,189 @@
 use std::marker::PhantomData;
 
-use crate::{booleans::{Bool, False, Not, Or, True}, functions::{AnyTrue, Filter, Function, Map, FlatMap}, lists::{Cons, List, Nil, StrRepr}, numbers::{N0, N1, N3, N4, N8, Number, PeanoAbsDiff, PeanoEqual, PeanoLT, Range, Successor, Zero, GEQZero}};
+use crate::{booleans::{And, Bool, False, Not, Or, True}, functions::{AllTrue, AnyTrue, Filter, Function, Map, FlatMap}, lists::{Cons, Length, List, ListConcat, Nil, StrRepr}, logic, m, make_list, numbers::{N0, N1, N2, N3, N4, N5, N6, N8, N9, N16, Number, PeanoAbsDiff, PeanoEqual, PeanoLT, Range, Successor, Zero, GEQZero}, pretty::Pretty, json::{JsonValue, ToJson}, gameoflife::Reify};
 
+pub mod working_regular_impl {
+    /// All solutions of the N-Queens problem as lists of (x, y), with exactly one queen per x.
+    /// Columns and diagonals that are already attacked are tracked as bitmasks
+    pub fn solve(n: usize) -> Vec<Vec<(usize, usize)>> {
+        assert!(n < 64, "board too large for the bitmask solver");
+
+        fn place(
+            n: usize,
+            x: usize,
+            columns: u64,
+            diagonals: u64,
+            anti_diagonals: u64,
+            queens: &mut Vec<(usize, usize)>,
+            solutions: &mut Vec<Vec<(usize, usize)>>,
+        ) {
+            if x == n {
+                solutions.push(queens.clone());
+                return;
+            }
+
+            let full = (1u64 << n) - 1;
+            // diagonal x + y is bit y of `diagonals` shifted by x, likewise x - y for `anti_diagonals`
+            let mut free = full & !(columns | diagonals | anti_diagonals);
+            while free != 0 {
+                let bit = free & free.wrapping_neg();
+                free ^= bit;
+
+                queens.push((x, bit.trailing_zeros() as usize));
+                place(
+                    n,
+                    x + 1,
+                    columns | bit,
+                    (diagonals | bit) >> 1,
+                    (anti_diagonals | bit) << 1,
+                    queens,
+                    solutions,
+                );
+                queens.pop();
+            }
+        }
+
+        let mut solutions = Vec::new();
+        place(n, 0, 0, 0, 0, &mut Vec::with_capacity(n), &mut solutions);
+        solutions
+    }
+
+    /// Does a queen on `a` attack (or occupy) `b`
+    pub fn queen_attacks(a: (usize, usize), b: (usize, usize)) -> bool {
+        a.0 == b.0 || a.1 == b.1 || a.0.abs_diff(b.0) == a.1.abs_diff(b.1)
+    }
+
+    /// All sets of `count` mutually non-attacking pieces on a `width` x `height` board that attack
+    /// every square, like `IndependentDominating`. The squares each piece covers are bitmasks
+    pub fn independent_dominating(
+        width: usize,
+        height: usize,
+        count: usize,
+        attacks: fn((usize, usize), (usize, usize)) -> bool,
+    ) -> Vec<Vec<(usize, usize)>> {
+        assert!(width * height <= 128, "board too large for the bitmask solver");
+
+        let squares: Vec<(usize, usize)> = (0..width).flat_map(|x| (0..height).map(move |y| (x, y))).collect();
+        let covers: Vec<u128> = squares
+            .iter()
+            .map(|&a| squares.iter().enumerate().filter(|&(_, &b)| attacks(a, b)).map(|(i, _)| 1u128 << i).sum())
+            .collect();
+        let full = if squares.len() == 128 { u128::MAX } else { (1u128 << squares.len()) - 1 };
+
+        fn pick(
+            start: usize,
+            count: usize,
+            uncovered: u128,
+            squares: &[(usize, usize)],
+            covers: &[u128],
+            chosen: &mut Vec<usize>,
+            solutions: &mut Vec<Vec<(usize, usize)>>,
+        ) {
+            if count == 0 {
+                if uncovered == 0 {
+                    solutions.push(chosen.iter().map(|&i| squares[i]).collect());
+                }
+                return;
+            }
+            for i in start..squares.len() {
+                // a square is safe if no chosen piece covers it
+                if chosen.iter().any(|&c| covers[c] & (1u128 << i) != 0) {
+                    continue;
+                }
+                chosen.push(i);
+                pick(i + 1, count - 1, uncovered & !covers[i], squares, covers, chosen, solutions);
+                chosen.pop();
+            }
+        }
+
+        let mut solutions = Vec::new();
+        pick(0, count, full, &squares, &covers, &mut Vec::with_capacity(count), &mut solutions);
+        solutions
+    }
+}
+
+/// A kind of chess piece. Its moves are given by its `Attacks` impls, which require `Piece`,
+/// and the solvers take any `P: Piece`
+pub trait Piece {
+    const NAME: &'static str;
+}
+
+/// Does a piece of this kind on (AX, AY) attack (or occupy) (BX, BY)?
+pub trait Attacks<AX, AY, BX, BY>: Piece {
+    type Output: Bool;
+}
+
+/// A piece of kind `P` standing on (X, Y)
 #[derive(Default)]
-pub struct Queen<X: Number, Y: Number>(PhantomData<X>, PhantomData<Y>);
+pub struct Placed<P, X: Number, Y: Number>(PhantomData<P>, PhantomData<X>, PhantomData<Y>);
 
-impl<X: Number, Y: Number> StrRepr for Queen<X, Y> {
+impl<P: Piece, X: Number, Y: Number> StrRepr for Placed<P, X, Y> {
     fn str_repr() -> String {
-        format!("Queen({}, {})", X::str_repr(), Y::str_repr())
+        format!("{}({}, {})", P::NAME, X::str_repr(), Y::str_repr())
+    }
+}
+impl<P, X: Number, Y: Number> Pretty for Placed<P, X, Y> {
+    fn pretty_at(_multiline: bool, _depth: usize) -> String {
+        format!("({}, {})", X::VALUE, Y::VALUE)
+    }
+}
+impl<P, X: Number, Y: Number> ToJson for Placed<P, X, Y> {
+    fn to_json() -> JsonValue {
+        JsonValue::point(X::VALUE, Y::VALUE)
     }
 }
+impl<P, X: Number, Y: Number> Reify for Placed<P, X, Y> {
+    type RuntimeValue = (usize, usize);
 
-pub trait Threatens<QueenA, QueenB> {
-    type Output: Bool;
+    fn reify() -> Self::RuntimeValue {
+        (X::VALUE as usize, Y::VALUE as usize)
+    }
+}
+
+#[derive(Default)]
+pub struct QueenPiece;
+#[derive(Default)]
+pub struct RookPiece;
+#[derive(Default)]
+pub struct BishopPiece;
+#[derive(Default)]
+pub struct KingPiece;
+#[derive(Default)]
+pub struct KnightPiece;
+/// Moves like a queen or a knight
+#[derive(Default)]
+pub struct SuperQueenPiece;
+
+impl Piece for QueenPiece {
+    const NAME: &'static str = "Queen";
 }
-impl<AX, AY, BX, BY> Threatens<Queen<AX, AY>, Queen<BX, BY>> for (Queen<AX, AY>, Queen<BX, BY>)
+impl Piece for RookPiece {
+    const NAME: &'static str = "Rook";
+}
+impl Piece for BishopPiece {
+    const NAME: &'static str = "Bishop";
+}
+impl Piece for KingPiece {
+    const NAME: &'static str = "King";
+}
+impl Piece for KnightPiece {
+    const NAME: &'static str = "Knight";
+}
+impl Piece for SuperQueenPiece {
+    const NAME: &'static str = "SuperQueen";
+}
+
+pub type Queen<X, Y> = Placed<QueenPiece, X, Y>;
+pub type Rook<X, Y> = Placed<RookPiece, X, Y>;
+pub type Bishop<X, Y> = Placed<BishopPiece, X, Y>;
+pub type King<X, Y> = Placed<KingPiece, X, Y>;
+pub type Knight<X, Y> = Placed<KnightPiece, X, Y>;
+pub type SuperQueen<X, Y> = Placed<SuperQueenPiece, X, Y>;
+
+/// |ax - bx|
+type DX<AX, BX> = <AX as PeanoAbsDiff<BX>>::AbsDiff;
+
+impl<AX, AY, BX, BY> Attacks<AX, AY, BX, BY> for QueenPiece
 where
     AX: NumberOps<BX>,
     AY: NumberOps<BY>,
@@ -32,6 +201,104 @@ where
     >>::Equal as Or<<<AX as PeanoEqual<BX>>::Equal as Or<<AY as PeanoEqual<BY>>::Equal>>::Or>>::Or;
 }
 
+impl<AX, AY, BX, BY> Attacks<AX, AY, BX, BY> for RookPiece
+where
+    AX: NumberOps<BX>,
+    AY: NumberOps<BY>,
+    BX: Number,
+    BY: Number,
+    // same row or same column
+    <AX as PeanoEqual<BX>>::Equal: Or<<AY as PeanoEqual<BY>>::Equal>,
+{
+    type Output = logic!(or m!(eq AX, BX), m!(eq AY, BY));
+}
+
+impl<AX, AY, BX, BY> Attacks<AX, AY, BX, BY> for BishopPiece
+where
+    AX: NumberOps<BX>,
+    AY: NumberOps<BY>,
+    BX: Number,
+    BY: Number,
+    // same diagonal, which includes the same square
+    DX<AX, BX>: PeanoEqual<DX<AY, BY>>,
+{
+    type Output = m!(eq DX<AX, BX>, DX<AY, BY>);
+}
+
+impl<AX, AY, BX, BY> Attacks<AX, AY, BX, BY> for KingPiece
+where
+    AX: NumberOps<BX>,
+    AY: NumberOps<BY>,
+    BX: Number,
+    BY: Number,
+    // |ax - bx| <= 1 and |ay - by| <= 1
+    DX<AX, BX>: PeanoLT<N2>,
+    DX<AY, BY>: PeanoLT<N2>,
+    m!(lt DX<AX, BX>, N2): And<m!(lt DX<AY, BY>, N2)>,
+{
+    type Output = logic!(and m!(lt DX<AX, BX>, N2), m!(lt DX<AY, BY>, N2));
+}
+
+/// (|dx|, |dy|) == (X, Y)
+type DeltaIs<DX, DY, X, Y> = logic!(and m!(eq DX, X), m!(eq DY, Y));
+
+/// Is (|dx|, |dy|) one of (1, 2), (2, 1) or (0, 0)
+pub trait KnightJump {
+    type Output: Bool;
+}
+impl<DX, DY> KnightJump for (DX, DY)
+where
+    DX: PeanoEqual<N1> + PeanoEqual<N2> + PeanoEqual<N0>,
+    DY: PeanoEqual<N1> + PeanoEqual<N2> + PeanoEqual<N0>,
+    m!(eq DX, N1): And<m!(eq DY, N2)>,
+    m!(eq DX, N2): And<m!(eq DY, N1)>,
+    m!(eq DX, N0): And<m!(eq DY, N0)>,
+    DeltaIs<DX, DY, N1, N2>: Or<DeltaIs<DX, DY, N2, N1>>,
+    logic!(or DeltaIs<DX, DY, N1, N2>, DeltaIs<DX, DY, N2, N1>): Or<DeltaIs<DX, DY, N0, N0>>,
+{
+    type Output = logic!(or
+        logic!(or DeltaIs<DX, DY, N1, N2>, DeltaIs<DX, DY, N2, N1>),
+        DeltaIs<DX, DY, N0, N0>
+    );
+}
+
+impl<AX, AY, BX, BY> Attacks<AX, AY, BX, BY> for KnightPiece
+where
+    AX: NumberOps<BX>,
+    AY: NumberOps<BY>,
+    BX: Number,
+    BY: Number,
+    (DX<AX, BX>, DX<AY, BY>): KnightJump,
+{
+    type Output = <(DX<AX, BX>, DX<AY, BY>) as KnightJump>::Output;
+}
+
+impl<AX, AY, BX, BY> Attacks<AX, AY, BX, BY> for SuperQueenPiece
+where
+    QueenPiece: Attacks<AX, AY, BX, BY>,
+    KnightPiece: Attacks<AX, AY, BX, BY>,
+    <QueenPiece as Attacks<AX, AY, BX, BY>>::Output: Or<<KnightPiece as Attacks<AX, AY, BX, BY>>::Output>,
+{
+    type Output = logic!(or
+        <QueenPiece as Attacks<AX, AY, BX, BY>>::Output,
+        <KnightPiece as Attacks<AX, AY, BX, BY>>::Output
+    );
+}
+
+pub trait Threatens<PieceA, PieceB> {
+    type Output: Bool;
+}
+impl<P, AX, AY, BX, BY> Threatens<Placed<P, AX, AY>, Placed<P, BX, BY>> for (Placed<P, AX, AY>, Placed<P, BX, BY>)
+where
+    AX: Number,
+    AY: Number,
+    BX: Number,
+    BY: Number,
+    P: Attacks<AX, AY, BX, BY>,
+{
+    type Output = <P as Attacks<AX, AY, BX, BY>>::Output;
+}
+
 fn threatens_type_test() {
     let _: <(Queen<N0, N0>, Queen<N0, N8>) as Threatens<_, _>>::Output = True;
     let _: <(Queen<N0, N0>, Queen<N4, N0>) as Threatens<_, _>>::Output = True;
@@ -40,56 +307,66 @@ fn threatens_type_test() {
     let _: <(Queen<N4, N3>, Queen<N0, N0>) as Threatens<_, _>>::Output = False;
     let _: <(Queen<N4, N3>, Queen<N1, N0>) as Threatens<_, _>>::Output = True;
     let _: <(Queen<N1, N0>, Queen<N4, N3>) as Threatens<_, _>>::Output = True;
+
+    let _: <(Rook<N0, N0>, Rook<N4, N4>) as Threatens<_, _>>::Output = False;
+    let _: <(Bishop<N0, N0>, Bishop<N0, N8>) as Threatens<_, _>>::Output = False;
+    let _: <(Bishop<N1, N0>, Bishop<N4, N3>) as Threatens<_, _>>::Output = True;
+    let _: <(King<N1, N1>, King<N0, N0>) as Threatens<_, _>>::Output = True;
+    let _: <(King<N2, N0>, King<N0, N0>) as Threatens<_, _>>::Output = False;
+    let _: <(Knight<N0, N0>, Knight<N1, N2>) as Threatens<_, _>>::Output = True;
+    let _: <(Knight<N0, N0>, Knight<N1, N1>) as Threatens<_, _>>::Output = False;
+    let _: <(SuperQueen<N0, N0>, SuperQueen<N2, N1>) as Threatens<_, _>>::Output = True;
 }
 
-pub struct Threatens1<ThisQueen>(PhantomData<ThisQueen>);
-impl<ThisQueen, OtherQueen> Function<OtherQueen> for Threatens1<ThisQueen>
+pub struct Threatens1<ThisPiece>(PhantomData<ThisPiece>);
+impl<ThisPiece, OtherPiece> Function<OtherPiece> for Threatens1<ThisPiece>
 where
-    (ThisQueen, OtherQueen): Threatens<ThisQueen, OtherQueen>,
+    (ThisPiece, OtherPiece): Threatens<ThisPiece, OtherPiece>,
 {
-    type Apply = <(ThisQueen, OtherQueen) as Threatens<ThisQueen, OtherQueen>>::Output;
+    type Apply = <(ThisPiece, OtherPiece) as Threatens<ThisPiece, OtherPiece>>::Output;
 }
 
-pub trait Safe<ExistingQueens, NewQueen> {
+pub trait Safe<ExistingPieces, NewPiece> {
     type Output: Bool;
 }
-impl<ExistingQueens: List, X: Number, Y: Number> Safe<ExistingQueens, Queen<X, Y>>
-    for (ExistingQueens, Queen<X, Y>)
+impl<ExistingPieces: List, P, X: Number, Y: Number> Safe<ExistingPieces, Placed<P, X, Y>>
+    for (ExistingPieces, Placed<P, X, Y>)
 where
-    ExistingQueens: Map<Threatens1<Queen<X, Y>>>,
-    <ExistingQueens as Map<Threatens1<Queen<X, Y>>>>::Output: AnyTrue,
-    <<ExistingQueens as Map<Threatens1<Queen<X, Y>>>>::Output as AnyTrue>::Output: Not,
+    ExistingPieces: Map<Threatens1<Placed<P, X, Y>>>,
+    <ExistingPieces as Map<Threatens1<Placed<P, X, Y>>>>::Output: AnyTrue,
+    <<ExistingPieces as Map<Threatens1<Placed<P, X, Y>>>>::Output as AnyTrue>::Output: Not,
 {
-    // It is safe to add a new queen if it is not threatened by any existing queen
+    // It is safe to add a new piece if it is not threatened by any existing piece
     type Output =
-        <<<ExistingQueens as Map<Threatens1<Queen<X, Y>>>>::Output as AnyTrue>::Output as Not>::Not;
+        <<<ExistingPieces as Map<Threatens1<Placed<P, X, Y>>>>::Output as AnyTrue>::Output as Not>::Not;
 }
 
 /// Partial application of `Safe`
 pub struct Safe1<Config>(PhantomData<Config>);
-impl<Config, NewQueen> Function<NewQueen> for Safe1<Config>
+impl<Config, NewPiece> Function<NewPiece> for Safe1<Config>
 where
-    (Config, NewQueen): Safe<Config, NewQueen>,
+    (Config, NewPiece): Safe<Config, NewPiece>,
 {
-    type Apply = <(Config, NewQueen) as Safe<Config, NewQueen>>::Output;
+    type Apply = <(Config, NewPiece) as Safe<Config, NewPiece>>::Output;
 }
 
-pub struct Queen1<X: Number>(PhantomData<X>);
-impl<X: Number, Y: Number> Function<Y> for Queen1<X> {
-    type Apply = Queen<X, Y>;
+pub struct Place1<P, X: Number>(PhantomData<P>, PhantomData<X>);
+impl<P, X: Number, Y: Number> Function<Y> for Place1<P, X> {
+    type Apply = Placed<P, X, Y>;
 }
+pub type Queen1<X> = Place1<QueenPiece, X>;
 
-/// Return a list of queens with given x pos and y in [0, NumQueens)
-pub trait QueensInRow<NumQueens: Number, XPos: Number> {
+/// Return a list of pieces with given x pos and y in [0, NumPieces)
+pub trait PiecesInRow<P, NumPieces: Number, XPos: Number> {
     type Output: List;
 }
-impl<NumQueens, XPos> QueensInRow<NumQueens, XPos> for (NumQueens, XPos)
+impl<P, NumPieces, XPos> PiecesInRow<P, NumPieces, XPos> for (P, NumPieces, XPos)
 where
-    NumQueens: Number + Range,
+    NumPieces: Number + Range,
     XPos: Number,
-    <NumQueens as Range>::Range: Map<Queen1<XPos>>,
+    <NumPieces as Range>::Range: Map<Place1<P, XPos>>,
 {
-    type Output = <<NumQueens as Range>::Range as Map<Queen1<XPos>>>::Output;
+    type Output = <<NumPieces as Range>::Range as Map<Place1<P, XPos>>>::Output;
 }
 
 /// List -> Item -> Cons<List, Item>
@@ -102,48 +379,48 @@ fn test_prepend() {
     // let _: <Prepend<Cons<N0, Cons<N0, Nil>>> as Function<N0>>::Apply = 0;
 }
 
-pub trait AddQueen<N: Number, X: Number, ExistingQueens: List> {
+pub trait AddPiece<P, N: Number, X: Number, ExistingPieces: List> {
     type Output;
 }
-impl<N: Number, X: Number, ExistingQueens: List> AddQueen<N, X, ExistingQueens> for ExistingQueens
+impl<P, N: Number, X: Number, ExistingPieces: List> AddPiece<P, N, X, ExistingPieces> for ExistingPieces
 where
-    (N, X): QueensInRow<N, X>,
-    <(N, X) as QueensInRow<N, X>>::Output: Filter<Safe1<ExistingQueens>>,
-    <<(N, X) as QueensInRow<N, X>>::Output as Filter<Safe1<ExistingQueens>>>::Output:
-        Map<Prepend<ExistingQueens>>,
+    (P, N, X): PiecesInRow<P, N, X>,
+    <(P, N, X) as PiecesInRow<P, N, X>>::Output: Filter<Safe1<ExistingPieces>>,
+    <<(P, N, X) as PiecesInRow<P, N, X>>::Output as Filter<Safe1<ExistingPieces>>>::Output:
+        Map<Prepend<ExistingPieces>>,
 {
     type Output =
-        <<<(N, X) as QueensInRow<N, X>>::Output as Filter<Safe1<ExistingQueens>>>::Output as Map<
-            Prepend<ExistingQueens>,
+        <<<(P, N, X) as PiecesInRow<P, N, X>>::Output as Filter<Safe1<ExistingPieces>>>::Output as Map<
+            Prepend<ExistingPieces>,
         >>::Output;
 }
 
-fn test_add_queen() {
-    // let _: <Nil as AddQueen<N3, N0, _>>::Output = 3;
+fn test_add_piece() {
+    // let _: <Nil as AddPiece<QueenPiece, N3, N0, _>>::Output = 3;
 }
 
-pub struct AddQueen2<N: Number, X: Number>(PhantomData<N>, PhantomData<X>);
-impl<N: Number, X: Number, ExistingQueens: List + AddQueen<N, X, ExistingQueens>>
-    Function<ExistingQueens> for AddQueen2<N, X>
+pub struct AddPiece2<P, N: Number, X: Number>(PhantomData<P>, PhantomData<N>, PhantomData<X>);
+impl<P, N: Number, X: Number, ExistingPieces: List + AddPiece<P, N, X, ExistingPieces>>
+    Function<ExistingPieces> for AddPiece2<P, N, X>
 {
-    type Apply = <ExistingQueens as AddQueen<N, X, ExistingQueens>>::Output;
+    type Apply = <ExistingPieces as AddPiece<P, N, X, ExistingPieces>>::Output;
 }
 
-fn test_add_queen2() {
-    // let _: <AddQueen2<N3, N0> as Function<Nil>>::Apply = 3;
+fn test_add_piece2() {
+    // let _: <AddPiece2<QueenPiece, N3, N0> as Function<Nil>>::Apply = 3;
 }
 
-pub trait AddQueenToAll<N: Number, X: Number, Configs: List> {
+pub trait AddPieceToAll<P, N: Number, X: Number, Configs: List> {
     type Output;
 }
-impl<N: Number, X: Number, Configs: List> AddQueenToAll<N, X, Configs> for Configs
+impl<P, N: Number, X: Number, Configs: List> AddPieceToAll<P, N, X, Configs> for Configs
 where
-    Configs: FlatMap<AddQueen2<N, X>>,
+    Configs: FlatMap<AddPiece2<P, N, X>>,
 {
-    // For each configuration, get valid configurations with a queen on row X
+    // For each configuration, get valid configurations with a piece on row X
     // And concatenate together the list of list of configurations, resulting in a
-    // list of configurations (list of list of queens)
-    type Output = <Configs as FlatMap<AddQueen2<N, X>>>::Output;
+    // list of configurations (list of list of pieces)
+    type Output = <Configs as FlatMap<AddPiece2<P, N, X>>>::Output;
 }
 
 struct CheckIsList;
@@ -151,56 +428,342 @@ impl<L: List> Function<L> for CheckIsList {
     type Apply = L;
 }
 
-fn test_add_queen_to_all() {
-    // List of List of Queens
-    type ListOfConfigurations = <AddQueen2<N4, N0> as Function<Nil>>::Apply;
+fn test_add_piece_to_all() {
+    // List of List of pieces
+    type ListOfConfigurations = <AddPiece2<QueenPiece, N4, N0> as Function<Nil>>::Apply;
     // ListOfConfigurations is indeed a list of lists
     // let _: <ListOfConfigurations as Map<CheckIsList>>::Output = 3;
     // let _: ListOfConfigurations = 3;
-    // let _: <ListOfConfigurations as AddQueenToAll<N4, N1, _>>::Output ;
+    // let _: <ListOfConfigurations as AddPieceToAll<N4, N1, _>>::Output ;
 }
 
-pub trait AddQueensIf<Predicate, N, X, Configs> {
+pub trait AddPiecesIf<Predicate, P, N, X, Configs> {
     type Output;
 }
 
-impl<N, X, Configs> AddQueensIf<False, N, X, Configs> for Configs {
+impl<P, N, X, Configs> AddPiecesIf<False, P, N, X, Configs> for Configs {
     type Output = Configs;
 }
-impl<N: Number, X: Number + GEQZero, Configs: List> AddQueensIf<True, N, X, Configs> for Configs
+impl<P, N: Number, X: Number + GEQZero, Configs: List> AddPiecesIf<True, P, N, X, Configs> for Configs
 where
-    Configs: AddQueenToAll<N, X, Configs>,
-    <Configs as AddQueenToAll<N, X, Configs>>::Output:
-        AddQueens<N, Successor<X>, <Configs as AddQueenToAll<N, X, Configs>>::Output>,
+    Configs: AddPieceToAll<P, N, X, Configs>,
+    <Configs as AddPieceToAll<P, N, X, Configs>>::Output:
+        AddPieces<P, N, Successor<X>, <Configs as AddPieceToAll<P, N, X, Configs>>::Output>,
 {
-    type Output = <<Configs as AddQueenToAll<N, X, Configs>>::Output as AddQueens<
+    type Output = <<Configs as AddPieceToAll<P, N, X, Configs>>::Output as AddPieces<
+        P,
         N,
         Successor<X>,
-        <Configs as AddQueenToAll<N, X, Configs>>::Output,
+        <Configs as AddPieceToAll<P, N, X, Configs>>::Output,
     >>::Output;
 }
 
 /// Given an N x N board, and a list of possible configurations
-/// Get configurations where queen on rows [X, N) have been placed down
-pub trait AddQueens<N, X, Configs> {
+/// Get configurations where pieces on rows [X, N) have been placed down
+pub trait AddPieces<P, N, X, Configs> {
     type Output;
 }
-impl<N: Number, X, Configs> AddQueens<N, X, Configs> for Configs
+impl<P, N: Number, X, Configs> AddPieces<P, N, X, Configs> for Configs
 where
     X: PeanoLT<N>,
-    Configs: AddQueensIf<<X as PeanoLT<N>>::LT, N, X, Configs>,
+    Configs: AddPiecesIf<<X as PeanoLT<N>>::LT, P, N, X, Configs>,
 {
-    type Output = <Configs as AddQueensIf<<X as PeanoLT<N>>::LT, N, X, Configs>>::Output;
+    type Output = <Configs as AddPiecesIf<<X as PeanoLT<N>>::LT, P, N, X, Configs>>::Output;
 }
 
-pub struct NQueensSolution;
-impl<N: Number> Function<N> for NQueensSolution
+/// All ways to put N mutually non-attacking pieces of kind `P` on an N x N board,
+/// one piece in every row
+pub struct NPiecesSolution<P>(PhantomData<P>);
+impl<P: Piece, N: Number> Function<N> for NPiecesSolution<P>
 where
     Zero: PeanoLT<N>,
-    Cons<Nil, Nil>: AddQueensIf<<Zero as PeanoLT<N>>::LT, N, Zero, Cons<Nil, Nil>>,
+    Cons<Nil, Nil>: AddPiecesIf<<Zero as PeanoLT<N>>::LT, P, N, Zero, Cons<Nil, Nil>>,
+{
+    type Apply = <Cons<Nil, Nil> as AddPieces<P, N, Zero, Cons<Nil, Nil>>>::Output;
+}
+
+pub type NQueensSolution = NPiecesSolution<QueenPiece>;
+
+fn piece_solutions_type_test() {
+    type Count<P, N> = <<NPiecesSolution<P> as Function<N>>::Apply as Length>::Length;
+
+    let _: Count<QueenPiece, N4> = N2::default();
+    let _: Count<RookPiece, N3> = N6::default();
+    let _: Count<BishopPiece, N3> = N5::default();
+    let _: Count<KingPiece, N3> = N2::default();
+    let _: Count<KnightPiece, N3> = N9::default();
+    let _: Count<SuperQueenPiece, N3> = N0::default();
+}
+
+/// X -> list of pieces with x pos X and y in [0, Height)
+pub struct RowOfPieces<P, Height>(PhantomData<P>, PhantomData<Height>);
+impl<P, Height: Number, X: Number> Function<X> for RowOfPieces<P, Height>
+where
+    (P, Height, X): PiecesInRow<P, Height, X>,
+{
+    type Apply = <(P, Height, X) as PiecesInRow<P, Height, X>>::Output;
+}
+
+/// Every square of a Width x Height board, holding a piece of kind `P`
+pub type BoardSquares<P, Width, Height> = <<Width as Range>::Range as FlatMap<RowOfPieces<P, Height>>>::Output;
+
+/// Configurations made by adding `Count` more pieces to `Config`, picked from the squares in `Self`.
+/// Pieces are only picked in the order of the square list, so every set of squares is found once
+pub trait PlaceFrom<Count, Config> {
+    type Output: List + ListConcat;
+}
+impl<Squares, Config> PlaceFrom<Zero, Config> for Squares {
+    type Output = Cons<Config, Nil>;
+}
+impl<Count: Number + GEQZero, Config> PlaceFrom<Successor<Count>, Config> for Nil {
+    type Output = Nil;
+}
+/// Configurations that use `Square` (if it is safe) followed by configurations that skip it
+type UsingSquare<Rest, Count, Config, Square> =
+    <Rest as PlaceIf<<(Config, Square) as Safe<Config, Square>>::Output, Count, Config, Square>>::Output;
+type SkippingSquare<Rest, Count, Config> = <Rest as PlaceFrom<Successor<Count>, Config>>::Output;
+
+impl<Count: Number + GEQZero, Config: List, Square, Rest> PlaceFrom<Successor<Count>, Config> for Cons<Square, Rest>
+where
+    (Config, Square): Safe<Config, Square>,
+    Rest: PlaceIf<<(Config, Square) as Safe<Config, Square>>::Output, Count, Config, Square>,
+    Rest: PlaceFrom<Successor<Count>, Config>,
+    <UsingSquare<Rest, Count, Config, Square> as ListConcat>::ConcatWith<SkippingSquare<Rest, Count, Config>>:
+        ListConcat,
 {
-    type Apply = <Cons<Nil, Nil> as AddQueens<N, Zero, Cons<Nil, Nil>>>::Output;
+    type Output = <UsingSquare<Rest, Count, Config, Square> as ListConcat>::ConcatWith<
+        SkippingSquare<Rest, Count, Config>,
+    >;
+}
+
+/// If `Square` is safe, place it and continue placing `Count` pieces from `Self`
+pub trait PlaceIf<Predicate, Count, Config, Square> {
+    type Output: List + ListConcat;
+}
+impl<Rest, Count, Config, Square> PlaceIf<False, Count, Config, Square> for Rest {
+    type Output = Nil;
+}
+impl<Rest: PlaceFrom<Count, Cons<Square, Config>>, Count, Config, Square> PlaceIf<True, Count, Config, Square> for Rest {
+    type Output = <Rest as PlaceFrom<Count, Cons<Square, Config>>>::Output;
+}
+
+/// All ways to put `Count` mutually non-attacking pieces of kind `Self` on a `Width` x `Height` board.
+/// Unlike `NPiecesSolution`, rows may be empty or hold several pieces
+pub trait PlacePieces<Width, Height, Count> {
+    type Output: List;
+}
+impl<P: Piece, Width, Height, Count> PlacePieces<Width, Height, Count> for P
+where
+    Width: Number + Range,
+    Height: Number,
+    <Width as Range>::Range: FlatMap<RowOfPieces<P, Height>>,
+    BoardSquares<P, Width, Height>: PlaceFrom<Count, Nil>,
+{
+    type Output = <BoardSquares<P, Width, Height> as PlaceFrom<Count, Nil>>::Output;
+}
+
+/// Config -> True if every square of the board is attacked or occupied by a piece in Config
+pub struct Dominates<P, Width, Height>(PhantomData<P>, PhantomData<Width>, PhantomData<Height>);
+/// Square -> True if some piece in Config attacks or occupies it
+pub struct Covered<Config>(PhantomData<Config>);
+impl<Config: Map<Threatens1<Square>>, Square> Function<Square> for Covered<Config>
+where
+    <Config as Map<Threatens1<Square>>>::Output: AnyTrue,
+{
+    type Apply = <<Config as Map<Threatens1<Square>>>::Output as AnyTrue>::Output;
+}
+impl<P: Piece, Width, Height, Config> Function<Config> for Dominates<P, Width, Height>
+where
+    Width: Number + Range,
+    <Width as Range>::Range: FlatMap<RowOfPieces<P, Height>>,
+    BoardSquares<P, Width, Height>: Map<Covered<Config>>,
+    <BoardSquares<P, Width, Height> as Map<Covered<Config>>>::Output: AllTrue,
+{
+    type Apply = <<BoardSquares<P, Width, Height> as Map<Covered<Config>>>::Output as AllTrue>::Output;
+}
+
+/// Configurations of `Count` non-attacking pieces that also attack every empty square.
+/// The 5 queens on 8 x 8 case has C(64, 5) candidate sets, far more than the compiler can search,
+/// so it is checked with `working_regular_impl::independent_dominating` instead
+pub trait IndependentDominating<Width, Height, Count> {
+    type Output: List;
+}
+impl<P: Piece, Width, Height, Count> IndependentDominating<Width, Height, Count> for P
+where
+    P: PlacePieces<Width, Height, Count>,
+    <P as PlacePieces<Width, Height, Count>>::Output: Filter<Dominates<P, Width, Height>>,
+    <<P as PlacePieces<Width, Height, Count>>::Output as Filter<Dominates<P, Width, Height>>>::Output: List,
+{
+    type Output = <<P as PlacePieces<Width, Height, Count>>::Output as Filter<Dominates<P, Width, Height>>>::Output;
+}
+
+fn place_pieces_type_test() {
+    type Count<L> = <L as Length>::Length;
+
+    let _: Count<<QueenPiece as PlacePieces<N4, N4, N4>>::Output> = N2::default();
+    let _: Count<<QueenPiece as PlacePieces<N3, N3, N2>>::Output> = N8::default();
+    let _: Count<<QueenPiece as PlacePieces<N4, N3, N3>>::Output> = N4::default();
+    let _: Count<<RookPiece as PlacePieces<N3, N2, N2>>::Output> = N6::default();
+    let _: Count<<KingPiece as PlacePieces<N3, N3, N1>>::Output> = N9::default();
+
+    let _: Count<<KingPiece as IndependentDominating<N3, N3, N1>>::Output> = N1::default();
+    let _: Count<<KingPiece as IndependentDominating<N3, N3, N2>>::Output> = N2::default();
+    let _: Count<<KnightPiece as IndependentDominating<N3, N3, N2>>::Output> = N0::default();
+    let _: Count<<QueenPiece as IndependentDominating<N4, N4, N3>>::Output> = N16::default();
+    let _: Count<<QueenPiece as IndependentDominating<N5, N3, N2>>::Output> = N4::default();
+}
+
+/// Queen -> True if the queen is on an N x N board. Coordinates must not be negative
+pub struct InBounds<N>(PhantomData<N>);
+impl<N: Number, P, X, Y> Function<Placed<P, X, Y>> for InBounds<N>
+where
+    X: Number + PeanoLT<N>,
+    Y: Number + PeanoLT<N>,
+    m!(lt X, N): And<m!(lt Y, N)>,
+{
+    type Apply = logic!(and m!(lt X, N), m!(lt Y, N));
+}
+
+/// True if no two pieces in the list threaten each other
+pub trait PairwiseSafe {
+    type Output: Bool;
+}
+impl PairwiseSafe for Nil {
+    type Output = True;
+}
+impl<Q, Rest: PairwiseSafe> PairwiseSafe for Cons<Q, Rest>
+where
+    (Rest, Q): Safe<Rest, Q>,
+    <(Rest, Q) as Safe<Rest, Q>>::Output: And<Rest::Output>,
+{
+    type Output = logic!(and <(Rest, Q) as Safe<Rest, Q>>::Output, Rest::Output);
+}
+
+type AllInBounds<N, Config> = <<Config as Map<InBounds<N>>>::Output as AllTrue>::Output;
+type HasNQueens<N, Config> = m!(eq <Config as Length>::Length, N);
+
+/// Config -> True if Config is a list of exactly N queens on an N x N board, none threatening another
+pub struct IsValidNQueens<N>(PhantomData<N>);
+impl<N: Number, Config> Function<Config> for IsValidNQueens<N>
+where
+    Config: Length + PairwiseSafe + Map<InBounds<N>>,
+    <Config as Map<InBounds<N>>>::Output: AllTrue,
+    <Config as Length>::Length: PeanoEqual<N>,
+    AllInBounds<N, Config>: And<HasNQueens<N, Config>>,
+    logic!(and AllInBounds<N, Config>, HasNQueens<N, Config>): And<<Config as PairwiseSafe>::Output>,
+{
+    type Apply = logic!(and
+        logic!(and AllInBounds<N, Config>, HasNQueens<N, Config>),
+        <Config as PairwiseSafe>::Output
+    );
+}
+
+#[diagnostic::on_unimplemented(
+    message = "this is not a valid N-Queens configuration",
+    label = "`IsValidNQueens` returned `{Self}`",
+    note = "a valid configuration has exactly N queens, all inside the N x N board, and none threatening another"
+)]
+pub trait ValidNQueens {}
+impl ValidNQueens for True {}
+
+/// Compile error unless the list of queens is a valid N-Queens configuration
+#[macro_export]
+macro_rules! assert_valid_nqueens {
+    ($n:ty, $config:ty) => {
+        const _: () = {
+            fn assert_valid<B: $crate::nqueens::ValidNQueens>() {}
+            let _ = assert_valid::<<$crate::nqueens::IsValidNQueens<$n> as $crate::functions::Function<$config>>::Apply>;
+        };
+    };
+}
+
+assert_valid_nqueens!(N4, make_list!(Queen<N0, N1>, Queen<N1, N3>, Queen<N2, N0>, Queen<N3, N2>));
+
+fn is_valid_nqueens_type_test() {
+    type Check<N, Config> = <IsValidNQueens<N> as Function<Config>>::Apply;
+
+    let _: Check<N1, make_list!(Queen<N0, N0>)> = True;
+    type Valid = make_list!(Queen<N0, N2>, Queen<N1, N0>, Queen<N2, N3>, Queen<N3, N1>);
+    let _: Check<N4, Valid> = True;
+    // a queen off the board
+    type OffBoard = make_list!(Queen<N0, N2>, Queen<N1, N0>, Queen<N2, N3>, Queen<N3, N5>);
+    let _: Check<N4, OffBoard> = False;
+    // too few queens
+    type TooFew = make_list!(Queen<N0, N2>, Queen<N1, N0>, Queen<N2, N3>);
+    let _: Check<N4, TooFew> = False;
+    // two queens on a diagonal
+    type Diagonal = make_list!(Queen<N0, N2>, Queen<N1, N0>, Queen<N2, N1>, Queen<N3, N3>);
+    let _: Check<N4, Diagonal> = False;
 }
 
 pub trait NumberOps<N: Number>: Number + PeanoEqual<N> + PeanoAbsDiff<N> {}
 impl<N1: Number, N2: Number + PeanoEqual<N1> + PeanoAbsDiff<N1>> NumberOps<N1> for N2 {}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::collections::BTreeSet;
+
+    use crate::{
+        functions::Function,
+        gameoflife::ReifyList,
+        nqueens::{
+            working_regular_impl::{independent_dominating, queen_attacks, solve},
+            IndependentDominating, NQueensSolution, QueenPiece,
+        },
+        numbers::{N1, N2, N3, N4, N5, N6},
+    };
+
+    pub(crate) type Board = BTreeSet<(usize, usize)>;
+
+    pub(crate) fn as_set(solutions: Vec<Vec<(usize, usize)>>) -> BTreeSet<Board> {
+        solutions.into_iter().map(|queens| queens.into_iter().collect()).collect()
+    }
+
+    /// The boards `Solver` finds for N, e.g. `NQueensSolution` or `symmetry::FundamentalSolutions`
+    pub(crate) fn type_level<Solver, N>() -> BTreeSet<Board>
+    where
+        Solver: Function<N>,
+        <Solver as Function<N>>::Apply: ReifyList<Vec<(usize, usize)>>,
+    {
+        as_set(<<Solver as Function<N>>::Apply as ReifyList<_>>::reify_list())
+    }
+
+    #[test]
+    fn test_runtime_counts() {
+        let counts: Vec<usize> = (1..=8).map(|n| solve(n).len()).collect();
+        assert_eq!(counts, vec![1, 0, 0, 2, 10, 4, 40, 92]);
+    }
+
+    #[test]
+    fn test_matches_type_level() {
+        let expected = [
+            type_level::<NQueensSolution, N1>(),
+            type_level::<NQueensSolution, N2>(),
+            type_level::<NQueensSolution, N3>(),
+            type_level::<NQueensSolution, N4>(),
+            type_level::<NQueensSolution, N5>(),
+            type_level::<NQueensSolution, N6>(),
+        ];
+        for (n, type_level) in (1..).zip(expected) {
+            let runtime = as_set(solve(n));
+            assert_eq!(runtime.len(), [1, 0, 0, 2, 10, 4][n - 1]);
+            assert_eq!(runtime, type_level, "N = {}", n);
+        }
+    }
+
+    fn dominating_queens<Width, Height, Count>() -> BTreeSet<Board>
+    where
+        QueenPiece: IndependentDominating<Width, Height, Count>,
+        <QueenPiece as IndependentDominating<Width, Height, Count>>::Output: ReifyList<Vec<(usize, usize)>>,
+    {
+        as_set(<<QueenPiece as IndependentDominating<Width, Height, Count>>::Output as ReifyList<_>>::reify_list())
+    }
+
+    #[test]
+    fn test_independent_dominating() {
+        assert_eq!(dominating_queens::<N4, N4, N3>(), as_set(independent_dominating(4, 4, 3, queen_attacks)));
+        assert_eq!(dominating_queens::<N5, N3, N2>(), as_set(independent_dominating(5, 3, 2, queen_attacks)));
+        // too large for the type-level search
+        assert_eq!(independent_dominating(8, 8, 5, queen_attacks).len(), 728);
+    }
+}
@@ -7,8 +7,16 @@ use crate::{
 };
 
 /// implemented for N >= 0
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a non-negative Number",
+    label = "expected `Zero` or `Successor<N>`"
+)]
 pub trait GEQZero {}
 /// implemented for N > 0
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a positive Number",
+    label = "expected `Successor<N>`"
+)]
 pub trait GTZero: GEQZero {}
 
 
@@ -24,6 +32,11 @@ impl<N: GEQZero> GTZero for Successor<N> {}
 #[derive(Default)]
 pub struct Negative<N: GTZero>(PhantomData<N>);
 
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a type-level Number",
+    label = "expected a Number",
+    note = "numbers are built from `Zero`, `Successor<N>` and `Negative<N>`"
+)]
 pub trait Number {
     const VALUE: isize;
 }
@@ -52,6 +65,37 @@ pub type N5 = Successor<N4>;
 pub type N6 = Successor<N5>;
 pub type N7 = Successor<N6>;
 pub type N8 = Successor<N7>;
+pub type N9 = Successor<N8>;
+pub type N10 = Successor<N9>;
+pub type N11 = Successor<N10>;
+pub type N12 = Successor<N11>;
+pub type N13 = Successor<N12>;
+pub type N14 = Successor<N13>;
+pub type N15 = Successor<N14>;
+pub type N16 = Successor<N15>;
+
+/// convert a regular usize into a funny type system number
+pub struct NumberWrapper<const VAL: usize>;
+/// Only implemented for 0 to 16 (`N0` to `N16`): every value needs its own impl, so larger
+/// const generics (e.g. array lengths) cannot be lifted
+pub trait LiftNumber {
+    type Value: Number + GEQZero;
+}
+
+macro_rules! lift_number_impl {
+    ($($val:literal => $t:ty,)*) => {
+        $(
+            impl LiftNumber for NumberWrapper<$val> {
+                type Value = $t;
+            }
+        )*
+    };
+}
+
+lift_number_impl!(
+    0 => N0, 1 => N1, 2 => N2, 3 => N3, 4 => N4, 5 => N5, 6 => N6, 7 => N7, 8 => N8,
+    9 => N9, 10 => N10, 11 => N11, 12 => N12, 13 => N13, 14 => N14, 15 => N15, 16 => N16,
+);
 
 #[macro_export]
 macro_rules! m {
@@ -61,11 +105,28 @@ macro_rules! m {
     (add $N:ty, $M:ty) => {
         <$N as $crate::numbers::PeanoAdd<$M>>::Val
     };
+    (sub $N:ty, $M:ty) => {
+        <$N as $crate::numbers::PeanoSub<$M>>::Val
+    };
+    (neg $N:ty) => {
+        <$N as $crate::numbers::Negate>::Negated
+    };
     (lt $N:ty, $M:ty) => {
         <$N as $crate::numbers::PeanoLT<$M>>::LT
     };
 }
 
+/// (A, B) -> A == B, the comparator to search lists of numbers with `gameoflife::Contains`
+pub struct PeanoEqualCmp;
+impl<A: PeanoEqual<B>, B: Number> Function<(A, B)> for PeanoEqualCmp {
+    type Apply = m!(eq A, B);
+}
+
+#[diagnostic::on_unimplemented(
+    message = "cannot compare `{Self}` with `{OtherNumber}` for equality",
+    label = "no `PeanoEqual<{OtherNumber}>` impl for `{Self}`",
+    note = "both sides must be Numbers built from `Zero`, `Successor<N>` and `Negative<N>`"
+)]
 pub trait PeanoEqual<OtherNumber: Number> {
     type Equal: Bool;
 }
@@ -112,6 +173,11 @@ impl<N: Number + GTZero, M: Number + PeanoAdd<N> + GEQZero> PeanoEqual<Successor
 }
 
 /// peano add
+#[diagnostic::on_unimplemented(
+    message = "cannot add `{OtherNumber}` to `{Self}`",
+    label = "no `PeanoAdd<{OtherNumber}>` impl for `{Self}`",
+    note = "both sides must be Numbers built from `Zero`, `Successor<N>` and `Negative<N>`"
+)]
 pub trait PeanoAdd<OtherNumber: Number> {
     type Val: Number;
 }
@@ -158,6 +224,36 @@ impl<N: Number + GTZero> PeanoAdd<Zero> for Negative<N>
     type Val = Self;
 }
 
+/// -n
+pub trait Negate {
+    type Negated: Number;
+}
+/// -0 = 0
+impl Negate for Zero {
+    type Negated = Zero;
+}
+/// -(n + 1)
+impl<N: Number + GEQZero> Negate for Successor<N> {
+    type Negated = Negative<Successor<N>>;
+}
+/// -(-n) = n
+impl<N: Number + GTZero> Negate for Negative<N> {
+    type Negated = N;
+}
+
+/// n - m = n + (-m)
+pub trait PeanoSub<OtherNumber: Number> {
+    type Val: Number;
+}
+impl<N: Number + PeanoAdd<m!(neg M)>, M: Number + Negate> PeanoSub<M> for N {
+    type Val = m!(add N, m!(neg M));
+}
+
+#[diagnostic::on_unimplemented(
+    message = "cannot decide whether `{Self}` < `{OtherNumber}`",
+    label = "no `PeanoLT<{OtherNumber}>` impl for `{Self}`",
+    note = "`PeanoLT` is only defined for non-negative Numbers (`Zero` and `Successor<N>`)"
+)]
 pub trait PeanoLT<OtherNumber: Number> {
     type LT: Bool;
 }
@@ -0,0 +1,66 @@
+//! type-level Option
+//!
+//! `Some` and `None` shadow the prelude, so import them with care in modules that also
+//! use the runtime `Option`.
+
+use std::marker::PhantomData;
+
+use crate::{
+    booleans::{Bool, False, True},
+    functions::Function,
+};
+
+#[derive(Default)]
+pub struct Some<T>(PhantomData<T>);
+#[derive(Default)]
+pub struct None;
+
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a type-level Option",
+    label = "expected `Some<T>` or `None`"
+)]
+pub trait TypeOption {
+    type IsSome: Bool;
+}
+impl<T> TypeOption for Some<T> {
+    type IsSome = True;
+}
+impl TypeOption for None {
+    type IsSome = False;
+}
+
+/// Apply `F` to the value inside `Some`
+pub trait OptionMap<F> {
+    type Output: TypeOption;
+}
+impl<F> OptionMap<F> for None {
+    type Output = None;
+}
+impl<F: Function<T>, T> OptionMap<F> for Some<T> {
+    type Output = Some<F::Apply>;
+}
+
+/// The value inside `Some`, or `Default` for `None`
+pub trait UnwrapOr<Default> {
+    type Output;
+}
+impl<Default> UnwrapOr<Default> for None {
+    type Output = Default;
+}
+impl<Default, T> UnwrapOr<Default> for Some<T> {
+    type Output = T;
+}
+
+fn option_type_test() {
+    use crate::{
+        functions::Increment,
+        numbers::{N0, N1, N2},
+    };
+
+    let _: <Some<N1> as TypeOption>::IsSome = True;
+    let _: <None as TypeOption>::IsSome = False;
+    let _: <Some<N1> as OptionMap<Increment>>::Output = Some::<N2>::default();
+    let _: <None as OptionMap<Increment>>::Output = None;
+    let _: <Some<N1> as UnwrapOr<N0>>::Output = N1::default();
+    let _: <None as UnwrapOr<N0>>::Output = N0::default();
+}
@@ -0,0 +1,184 @@
+//! human readable rendering of type-level values
+//!
+//! `StrRepr` falls back to `std::any::type_name`, which spells out every module path
+//! and every `Successor`. `Pretty` renders numbers as decimals, bools as `true`/`false`,
+//! lists as `[...]` and coordinates as tuples.
+
+use crate::{
+    booleans::{False, True},
+    lists::{Cons, Nil},
+    numbers::Number,
+};
+
+const INDENT: &str = "  ";
+
+pub trait Pretty {
+    /// `true` for `Nil` and `Cons`, so a list knows whether it contains nested lists
+    const IS_LIST: bool = false;
+
+    /// Render the value as if it were nested `depth` lists deep.
+    /// If `multiline` is set, lists containing other lists put each item on its own line
+    fn pretty_at(_multiline: bool, _depth: usize) -> String {
+        strip_module_paths(std::any::type_name::<Self>())
+    }
+
+    /// single line rendering
+    fn pretty() -> String {
+        Self::pretty_at(false, 0)
+    }
+
+    /// multi-line rendering, with nested lists indented
+    fn pretty_multiline() -> String {
+        Self::pretty_at(true, 0)
+    }
+}
+
+/// `a::b::C<d::E, F>` -> `C<E, F>`
+pub fn strip_module_paths(type_name: &str) -> String {
+    let mut ret = String::new();
+    let mut segment = String::new();
+    let mut chars = type_name.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == ':' && chars.peek() == Some(&':') {
+            // everything before a `::` is a module (or type) path, so forget it
+            chars.next();
+            segment.clear();
+        } else if c.is_alphanumeric() || c == '_' {
+            segment.push(c);
+        } else {
+            ret.push_str(&segment);
+            segment.clear();
+            ret.push(c);
+        }
+    }
+    ret.push_str(&segment);
+    ret
+}
+
+impl<N: Number> Pretty for N {
+    fn pretty_at(_multiline: bool, _depth: usize) -> String {
+        N::VALUE.to_string()
+    }
+}
+
+impl Pretty for True {
+    fn pretty_at(_multiline: bool, _depth: usize) -> String {
+        "true".into()
+    }
+}
+impl Pretty for False {
+    fn pretty_at(_multiline: bool, _depth: usize) -> String {
+        "false".into()
+    }
+}
+
+trait PrettyItems {
+    const HAS_LIST_ITEM: bool;
+    fn pretty_items(multiline: bool, depth: usize) -> Vec<String>;
+}
+impl PrettyItems for Nil {
+    const HAS_LIST_ITEM: bool = false;
+    fn pretty_items(_multiline: bool, _depth: usize) -> Vec<String> {
+        vec![]
+    }
+}
+impl<Item: Pretty, Rest: PrettyItems> PrettyItems for Cons<Item, Rest> {
+    const HAS_LIST_ITEM: bool = Item::IS_LIST || Rest::HAS_LIST_ITEM;
+    fn pretty_items(multiline: bool, depth: usize) -> Vec<String> {
+        let mut rest = Rest::pretty_items(multiline, depth);
+        rest.insert(0, Item::pretty_at(multiline, depth));
+        rest
+    }
+}
+
+impl Pretty for Nil {
+    const IS_LIST: bool = true;
+    fn pretty_at(_multiline: bool, _depth: usize) -> String {
+        "[]".into()
+    }
+}
+impl<I, R> Pretty for Cons<I, R>
+where
+    Cons<I, R>: PrettyItems,
+{
+    const IS_LIST: bool = true;
+    fn pretty_at(multiline: bool, depth: usize) -> String {
+        let items = Self::pretty_items(multiline, depth + 1);
+        if multiline && Self::HAS_LIST_ITEM {
+            let indent = INDENT.repeat(depth + 1);
+            let lines: Vec<String> = items.iter().map(|item| format!("{}{}", indent, item)).collect();
+            format!("[\n{}\n{}]", lines.join(",\n"), INDENT.repeat(depth))
+        } else {
+            format!("[{}]", items.join(", "))
+        }
+    }
+}
+
+macro_rules! tuple_impl_pretty {
+    ($($t:ident,)*) => {
+        impl<$($t,)*> Pretty for ($($t,)*) where
+            $(
+                $t: Pretty,
+            )*
+        {
+            fn pretty_at(multiline: bool, depth: usize) -> String {
+                let items: Vec<String> = vec![$(<$t as Pretty>::pretty_at(multiline, depth),)*];
+                format!("({})", items.join(", "))
+            }
+        }
+    };
+}
+
+tuple_impl_pretty!(T1,);
+tuple_impl_pretty!(T1,T2,);
+tuple_impl_pretty!(T1,T2,T3,);
+tuple_impl_pretty!(T1,T2,T3,T4,);
+tuple_impl_pretty!(T1,T2,T3,T4,T5,);
+tuple_impl_pretty!(T1,T2,T3,T4,T5,T6,);
+tuple_impl_pretty!(T1,T2,T3,T4,T5,T6,T7,);
+tuple_impl_pretty!(T1,T2,T3,T4,T5,T6,T7,T8,);
+tuple_impl_pretty!(T1,T2,T3,T4,T5,T6,T7,T8,T9,);
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        booleans::{False, True},
+        gameoflife::Cell,
+        make_list,
+        nqueens::Queen,
+        numbers::{Negative, N0, N1, N2, N3},
+        pretty::{strip_module_paths, Pretty},
+    };
+
+    #[test]
+    fn test_scalars() {
+        assert_eq!(N3::pretty(), "3");
+        assert_eq!(<Negative<N2>>::pretty(), "-2");
+        assert_eq!(True::pretty(), "true");
+        assert_eq!(False::pretty(), "false");
+        assert_eq!(Queen::<N1, N2>::pretty(), "(1, 2)");
+        assert_eq!(Cell::<Negative<N1>, N0>::pretty(), "(-1, 0)");
+        assert_eq!(<(N1, True)>::pretty(), "(1, true)");
+    }
+
+    #[test]
+    fn test_unknown_types() {
+        struct Apple;
+        impl Pretty for Apple {}
+
+        assert_eq!(Apple::pretty(), "Apple");
+        assert_eq!(strip_module_paths("a::b::C<d::E, F>"), "C<E, F>");
+    }
+
+    #[test]
+    fn test_lists() {
+        type Flat = make_list!(N0, N1, N2);
+        type Nested = make_list!(make_list!(Queen<N0, N1>, Queen<N1, N3>), make_list!());
+
+        assert_eq!(Flat::pretty(), "[0, 1, 2]");
+        assert_eq!(Flat::pretty_multiline(), "[0, 1, 2]");
+        assert_eq!(Nested::pretty(), "[[(0, 1), (1, 3)], []]");
+        assert_eq!(Nested::pretty_multiline(), "[\n  [(0, 1), (1, 3)],\n  []\n]");
+    }
+}
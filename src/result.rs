@@ -0,0 +1,164 @@
+//! type-level Result, for functions that are only defined on part of their domain
+//!
+//! `Err` carries a tag whose `StrRepr` is the error message. `Ok` and `Err` shadow the prelude,
+//! so import them with care in modules that also use the runtime `Result`.
+
+use std::marker::PhantomData;
+
+use crate::{
+    booleans::{Bool, False, True},
+    functions::Function,
+    lists::{Cons, List, Nil, StrRepr},
+};
+
+#[derive(Default)]
+pub struct Ok<T>(PhantomData<T>);
+#[derive(Default)]
+pub struct Err<E>(PhantomData<E>);
+
+/// Either is spelled with the same types, so every combinator here works on both.
+/// `Left<E>` is an alias of `Err<E>`: `AndThen` skips it and `MapErr` maps it
+pub type Left<E> = Err<E>;
+/// `Right<T>` is an alias of `Ok<T>`, the branch `AndThen` continues with
+pub type Right<T> = Ok<T>;
+
+impl<T: StrRepr> StrRepr for Ok<T> {
+    fn str_repr() -> String {
+        format!("Ok({})", T::str_repr())
+    }
+}
+impl<E: StrRepr> StrRepr for Err<E> {
+    fn str_repr() -> String {
+        format!("Err({})", E::str_repr())
+    }
+}
+
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a type-level Result",
+    label = "expected `Ok<T>` or `Err<E>`"
+)]
+pub trait TypeResult {
+    type IsOk: Bool;
+}
+impl<T> TypeResult for Ok<T> {
+    type IsOk = True;
+}
+impl<E> TypeResult for Err<E> {
+    type IsOk = False;
+}
+
+/// The value inside `Ok`. Does not compile on `Err`, and the error shows the tag
+#[diagnostic::on_unimplemented(
+    message = "unwrapped `{Self}`",
+    label = "expected `Ok<T>`"
+)]
+pub trait Unwrap {
+    type Output;
+}
+impl<T> Unwrap for Ok<T> {
+    type Output = T;
+}
+
+/// Apply `F`, which returns a Result, to the value inside `Ok`
+pub trait AndThen<F> {
+    type Output: TypeResult;
+}
+impl<F: Function<T, Apply: TypeResult>, T> AndThen<F> for Ok<T> {
+    type Output = F::Apply;
+}
+impl<F, E> AndThen<F> for Err<E> {
+    type Output = Err<E>;
+}
+
+/// Apply `F` to the tag inside `Err`
+pub trait MapErr<F> {
+    type Output: TypeResult;
+}
+impl<F, T> MapErr<F> for Ok<T> {
+    type Output = Ok<T>;
+}
+impl<F: Function<E>, E> MapErr<F> for Err<E> {
+    type Output = Err<F::Apply>;
+}
+
+/// A list of Results -> `Ok` of the list of values, or the first `Err`
+pub trait Sequence {
+    type Output: TypeResult;
+}
+impl Sequence for Nil {
+    type Output = Ok<Nil>;
+}
+impl<R: SequenceOnto<XS::Output>, XS: Sequence> Sequence for Cons<R, XS> {
+    type Output = R::Output;
+}
+
+/// Prepend the value of `Self` to the sequenced rest of the list
+pub trait SequenceOnto<Rest> {
+    type Output: TypeResult;
+}
+impl<T, Values: List> SequenceOnto<Ok<Values>> for Ok<T> {
+    type Output = Ok<Cons<T, Values>>;
+}
+impl<T, E> SequenceOnto<Err<E>> for Ok<T> {
+    type Output = Err<E>;
+}
+impl<E, Rest> SequenceOnto<Rest> for Err<E> {
+    type Output = Err<E>;
+}
+
+fn result_type_test() {
+    use crate::{
+        functions::{Const, Decrement, Increment, Underflow},
+        lists::{EmptyList, First},
+        make_list,
+        numbers::{Negative, N0, N1, N2},
+    };
+
+    struct Checked;
+    impl<T> Function<T> for Checked {
+        type Apply = Ok<T>;
+    }
+    #[derive(Default)]
+    struct Tag;
+
+    let _: <Ok<N1> as TypeResult>::IsOk = True;
+    let _: <Left<N1> as TypeResult>::IsOk = False;
+    let _: <Ok<N1> as Unwrap>::Output = N1::default();
+
+    let _: <Ok<N1> as AndThen<Checked>>::Output = Ok::<N1>::default();
+    let _: <Ok<N1> as AndThen<Const<Err<Tag>>>>::Output = Err::<Tag>::default();
+    let _: <Err<Tag> as AndThen<Checked>>::Output = Err::<Tag>::default();
+    let _: <Right<N0> as MapErr<Increment>>::Output = Right::<N0>::default();
+    let _: <Left<N0> as MapErr<Increment>>::Output = Left::<N1>::default();
+
+    type AllOk = make_list!(Ok<N1>, Ok<N2>);
+    type SomeErr = make_list!(Ok<N1>, Err<Tag>, Err<N0>);
+    let _: <AllOk as Sequence>::Output = Ok::<make_list!(N1, N2)>::default();
+    let _: <SomeErr as Sequence>::Output = Err::<Tag>::default();
+    let _: <Nil as Sequence>::Output = Ok::<Nil>::default();
+
+    let _: <make_list!(N1) as First>::First = Ok::<N1>::default();
+    let _: <Nil as First>::First = Err::<EmptyList>::default();
+
+    type TwiceDecremented<N> = <<Ok<N> as AndThen<Decrement>>::Output as AndThen<Decrement>>::Output;
+    let _: TwiceDecremented<N2> = Ok::<N0>::default();
+    let _: TwiceDecremented<N1> = Err::<Underflow>::default();
+    let _: <Decrement as Function<Negative<N1>>>::Apply = Ok::<Negative<N2>>::default();
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        functions::Underflow,
+        lists::{EmptyList, StrRepr},
+        numbers::N3,
+        result::{Err, Ok},
+    };
+
+    #[test]
+    fn test_error_messages() {
+        assert_eq!(<Ok<N3> as StrRepr>::str_repr(), "Ok(3)");
+        assert_eq!(<Err<EmptyList> as StrRepr>::str_repr(), "Err(First of an empty list)");
+        assert_eq!(<Err<Underflow> as StrRepr>::str_repr(), "Err(Decrement of zero)");
+    }
+}
@@ -0,0 +1,319 @@
+//! boolean satisfiability solved inside the type system
+//!
+//! A formula in conjunctive normal form is a list of clauses, and a clause is a list of
+//! literals. Variable `N` (N >= 1) is the literal `N`, and its negation is `Negative<N>`.
+//! `Solve` runs a DPLL search: it assigns a literal from a unit clause if there is one and
+//! otherwise tries the first literal of the first clause, then its negation.
+
+use std::marker::PhantomData;
+
+use crate::{
+    booleans::{False, Not, True},
+    func_call,
+    functions::{AnyTrue, Filter, Function, Map},
+    gameoflife::Contains,
+    lists::{Cons, First, List, Nil},
+    m,
+    numbers::{Negate, Number, PeanoEqual, PeanoEqualCmp},
+    result::Unwrap,
+};
+
+pub mod working_regular_impl {
+    /// Does every clause contain a literal of the assignment
+    pub fn satisfies(clauses: &[Vec<isize>], assignment: &[isize]) -> bool {
+        let consistent = assignment.iter().all(|literal| !assignment.contains(&-literal));
+        consistent && clauses.iter().all(|clause| clause.iter().any(|literal| assignment.contains(literal)))
+    }
+}
+
+/// Write a formula with integer literals, e.g. `cnf![[1, -2], [2, 3]]` for
+/// (x1 or not x2) and (x2 or x3). Variables go from 1 to 16
+#[macro_export]
+macro_rules! cnf {
+    (@clause [$($done:ty),*]) => {
+        $crate::make_list!($($done),*)
+    };
+    (@clause [$($done:ty),*] - $n:literal $(, $($rest:tt)*)?) => {
+        $crate::cnf!(@clause [$($done,)* $crate::numbers::Negative<$crate::cnf!(@var $n)>] $($($rest)*)?)
+    };
+    (@clause [$($done:ty),*] $n:literal $(, $($rest:tt)*)?) => {
+        $crate::cnf!(@clause [$($done,)* $crate::cnf!(@var $n)] $($($rest)*)?)
+    };
+    (@var $n:literal) => {
+        <$crate::numbers::NumberWrapper<$n> as $crate::numbers::LiftNumber>::Value
+    };
+    ($([$($literal:tt)*]),* $(,)?) => {
+        $crate::make_list!($($crate::cnf!(@clause [] $($literal)*)),*)
+    };
+}
+
+/// Result of solving a formula that has no satisfying assignment
+#[derive(Default)]
+pub struct Unsat;
+
+/// Clause -> True if it has no literals left
+pub struct IsEmptyClause;
+impl Function<Nil> for IsEmptyClause {
+    type Apply = True;
+}
+impl<L, Rest> Function<Cons<L, Rest>> for IsEmptyClause {
+    type Apply = False;
+}
+
+/// Clause -> False if the clause contains `L`
+pub struct NotSatisfiedBy<L>(PhantomData<L>);
+impl<L: Number, Clause> Function<Clause> for NotSatisfiedBy<L>
+where
+    Contains<PeanoEqualCmp>: Function<(Clause, L), Apply: Not>,
+{
+    type Apply = <func_call!(Contains<PeanoEqualCmp>[Clause, L]) as Not>::Not;
+}
+
+/// Literal -> True if it is not `L`
+pub struct Differs<L>(PhantomData<L>);
+impl<L: Number, X: PeanoEqual<L>> Function<X> for Differs<L>
+where
+    m!(eq X, L): Not,
+{
+    type Apply = <m!(eq X, L) as Not>::Not;
+}
+
+/// Clause -> the clause without the literal `L`
+pub struct WithoutLiteral<L>(PhantomData<L>);
+impl<L, Clause: Filter<Differs<L>>> Function<Clause> for WithoutLiteral<L> {
+    type Apply = <Clause as Filter<Differs<L>>>::Output;
+}
+
+/// Make `L` true: drop the clauses it satisfies and remove its negation from the others
+pub trait Assign<L> {
+    type Output;
+}
+impl<F: Filter<NotSatisfiedBy<L>>, L: Negate> Assign<L> for F
+where
+    <F as Filter<NotSatisfiedBy<L>>>::Output: Map<WithoutLiteral<m!(neg L)>>,
+{
+    type Output = <<F as Filter<NotSatisfiedBy<L>>>::Output as Map<WithoutLiteral<m!(neg L)>>>::Output;
+}
+
+/// The literal of the first clause with a single literal, or `Default` if there is none
+pub trait UnitLiteral<Default> {
+    type Output;
+}
+impl<Default> UnitLiteral<Default> for Nil {
+    type Output = Default;
+}
+impl<Default, Rest: UnitLiteral<Default>> UnitLiteral<Default> for Cons<Nil, Rest> {
+    type Output = Rest::Output;
+}
+impl<Default, L, Rest> UnitLiteral<Default> for Cons<Cons<L, Nil>, Rest> {
+    type Output = L;
+}
+impl<Default, L1, L2, Ls, Rest: UnitLiteral<Default>> UnitLiteral<Default> for Cons<Cons<L1, Cons<L2, Ls>>, Rest> {
+    type Output = Rest::Output;
+}
+
+/// Only used once there is no empty clause, so both unwraps succeed
+type FirstClause<F> = <<F as First>::First as Unwrap>::Output;
+type FirstLiteral<F> = <<FirstClause<F> as First>::First as Unwrap>::Output;
+/// The literal DPLL assigns next
+type Chosen<F> = <F as UnitLiteral<FirstLiteral<F>>>::Output;
+
+/// A satisfying assignment extending `Assignment`, or `Unsat`. The assignment lists the
+/// literals made true, most recent first. Variables that are not in it can take any value
+pub trait Solve<Assignment> {
+    type Output;
+}
+impl<Assignment: List> Solve<Assignment> for Nil {
+    type Output = Assignment;
+}
+impl<Clause, Rest, Assignment> Solve<Assignment> for Cons<Clause, Rest>
+where
+    Self: Map<IsEmptyClause>,
+    <Self as Map<IsEmptyClause>>::Output: AnyTrue,
+    Self: SolveIf<<<Self as Map<IsEmptyClause>>::Output as AnyTrue>::Output, Assignment>,
+{
+    type Output = <Self as SolveIf<<<Self as Map<IsEmptyClause>>::Output as AnyTrue>::Output, Assignment>>::Output;
+}
+
+/// `Solve` once it is known whether the formula has an empty clause
+pub trait SolveIf<HasEmptyClause, Assignment> {
+    type Output;
+}
+impl<F, Assignment> SolveIf<True, Assignment> for F {
+    type Output = Unsat;
+}
+impl<F, Assignment> SolveIf<False, Assignment> for F
+where
+    F: First + UnitLiteral<FirstLiteral<F>> + Branch<Chosen<F>, Assignment>,
+    <F as First>::First: Unwrap,
+    FirstClause<F>: First,
+    <FirstClause<F> as First>::First: Unwrap,
+{
+    type Output = <F as Branch<Chosen<F>, Assignment>>::Output;
+}
+
+/// Try to solve with `L` true, then with `L` false
+pub trait Branch<L, Assignment> {
+    type Output;
+}
+impl<F: Assign<L>, L: Negate, Assignment> Branch<L, Assignment> for F
+where
+    <F as Assign<L>>::Output: Solve<Cons<L, Assignment>>,
+    <<F as Assign<L>>::Output as Solve<Cons<L, Assignment>>>::Output: Otherwise<F, m!(neg L), Assignment>,
+{
+    type Output = <<<F as Assign<L>>::Output as Solve<Cons<L, Assignment>>>::Output as Otherwise<
+        F,
+        m!(neg L),
+        Assignment,
+    >>::Output;
+}
+
+/// Keep a solution, or solve `F` with `L` true if the first branch was `Unsat`
+pub trait Otherwise<F, L, Assignment> {
+    type Output;
+}
+impl<F: Assign<L>, L, Assignment> Otherwise<F, L, Assignment> for Unsat
+where
+    <F as Assign<L>>::Output: Solve<Cons<L, Assignment>>,
+{
+    type Output = <<F as Assign<L>>::Output as Solve<Cons<L, Assignment>>>::Output;
+}
+impl<F, L, Assignment> Otherwise<F, L, Assignment> for Nil {
+    type Output = Nil;
+}
+impl<F, L, Assignment, X, XS> Otherwise<F, L, Assignment> for Cons<X, XS> {
+    type Output = Cons<X, XS>;
+}
+
+/// Formula -> a satisfying assignment or `Unsat`
+pub struct Sat;
+impl<F: Solve<Nil>> Function<F> for Sat {
+    type Apply = <F as Solve<Nil>>::Output;
+}
+
+/// The 4-Queens problem. Variable 4 * row + column + 1 is true if there is a queen on the square
+pub type FourQueens = cnf![
+    [1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12], [13, 14, 15, 16],
+    [-1, -2], [-1, -3], [-1, -4], [-1, -5], [-1, -6], [-1, -9], [-1, -11], [-1, -13],
+    [-1, -16], [-2, -3], [-2, -4], [-2, -5], [-2, -6], [-2, -7], [-2, -10], [-2, -12],
+    [-2, -14], [-3, -4], [-3, -6], [-3, -7], [-3, -8], [-3, -9], [-3, -11], [-3, -15],
+    [-4, -7], [-4, -8], [-4, -10], [-4, -12], [-4, -13], [-4, -16], [-5, -6], [-5, -7],
+    [-5, -8], [-5, -9], [-5, -10], [-5, -13], [-5, -15], [-6, -7], [-6, -8], [-6, -9],
+    [-6, -10], [-6, -11], [-6, -14], [-6, -16], [-7, -8], [-7, -10], [-7, -11], [-7, -12],
+    [-7, -13], [-7, -15], [-8, -11], [-8, -12], [-8, -14], [-8, -16], [-9, -10], [-9, -11],
+    [-9, -12], [-9, -13], [-9, -14], [-10, -11], [-10, -12], [-10, -13], [-10, -14], [-10, -15],
+    [-11, -12], [-11, -14], [-11, -15], [-11, -16], [-12, -15], [-12, -16], [-13, -14], [-13, -15],
+    [-13, -16], [-14, -15], [-14, -16], [-15, -16],
+];
+
+/// 3-coloring of a 4-cycle 0-1-2-3 with the chord 0-2. Variable 3 * vertex + color + 1 is true
+/// if the vertex has that color
+pub type ThreeColoring = cnf![
+    [1, 2, 3], [4, 5, 6], [7, 8, 9], [10, 11, 12], [-1, -2], [-1, -3],
+    [-2, -3], [-4, -5], [-4, -6], [-5, -6], [-7, -8], [-7, -9],
+    [-8, -9], [-10, -11], [-10, -12], [-11, -12], [-1, -4], [-2, -5],
+    [-3, -6], [-4, -7], [-5, -8], [-6, -9], [-7, -10], [-8, -11],
+    [-9, -12], [-10, -1], [-11, -2], [-12, -3], [-1, -7], [-2, -8],
+    [-3, -9],
+];
+
+/// 2-coloring of a triangle, which is impossible. Variable 2 * vertex + color + 1 is true if
+/// the vertex has that color
+pub type TriangleTwoColoring = cnf![
+    [1, 2], [3, 4], [5, 6], [-1, -2], [-3, -4], [-5, -6],
+    [-1, -3], [-2, -4], [-3, -5], [-4, -6], [-5, -1], [-6, -2],
+];
+
+/// Runtime values of literals, clauses and results
+pub trait Literals {
+    fn literals() -> Vec<isize>;
+}
+impl Literals for Nil {
+    fn literals() -> Vec<isize> {
+        vec![]
+    }
+}
+impl<L: Number, Rest: Literals> Literals for Cons<L, Rest> {
+    fn literals() -> Vec<isize> {
+        let mut literals = Rest::literals();
+        literals.insert(0, L::VALUE);
+        literals
+    }
+}
+
+pub trait Clauses {
+    fn clauses() -> Vec<Vec<isize>>;
+}
+impl Clauses for Nil {
+    fn clauses() -> Vec<Vec<isize>> {
+        vec![]
+    }
+}
+impl<Clause: Literals, Rest: Clauses> Clauses for Cons<Clause, Rest> {
+    fn clauses() -> Vec<Vec<isize>> {
+        let mut clauses = Rest::clauses();
+        clauses.insert(0, Clause::literals());
+        clauses
+    }
+}
+
+pub trait SatResult {
+    fn assignment() -> Option<Vec<isize>>;
+}
+impl SatResult for Unsat {
+    fn assignment() -> Option<Vec<isize>> {
+        None
+    }
+}
+impl<Assignment: Literals> SatResult for Assignment {
+    fn assignment() -> Option<Vec<isize>> {
+        Some(Assignment::literals())
+    }
+}
+
+fn sat_type_test() {
+    use crate::{
+        make_list,
+        numbers::{Negative, N1, N2, N3},
+    };
+
+    let _: cnf![[1, -2], []] = <make_list!(make_list!(N1, Negative<N2>), Nil)>::default();
+
+    type Solved<F> = <Sat as Function<F>>::Apply;
+    let _: Solved<cnf![]> = Nil;
+    let _: Solved<cnf![[]]> = Unsat;
+    type Propagated = cnf![[1], [-1, 2]];
+    let _: Solved<Propagated> = <make_list!(N2, N1)>::default();
+    type Contradiction = cnf![[1], [-1]];
+    let _: Solved<Contradiction> = Unsat;
+    // x1 is tried first and fails, then x1 = false forces x3
+    type Backtracks = cnf![[1, 3], [-1, 2], [-1, -2]];
+    let _: Solved<Backtracks> = <make_list!(N3, Negative<N1>)>::default();
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        functions::Function,
+        sat::{working_regular_impl::satisfies, Clauses, FourQueens, Sat, SatResult, ThreeColoring, TriangleTwoColoring},
+    };
+
+    #[test]
+    fn test_four_queens() {
+        let assignment = <Sat as Function<FourQueens>>::Apply::assignment().unwrap();
+        assert!(satisfies(&FourQueens::clauses(), &assignment));
+
+        let mut queens: Vec<(isize, isize)> =
+            assignment.iter().filter(|&&literal| literal > 0).map(|literal| ((literal - 1) / 4, (literal - 1) % 4)).collect();
+        queens.sort();
+        assert!(queens == [(0, 1), (1, 3), (2, 0), (3, 2)] || queens == [(0, 2), (1, 0), (2, 3), (3, 1)]);
+    }
+
+    #[test]
+    fn test_coloring() {
+        let assignment = <Sat as Function<ThreeColoring>>::Apply::assignment().unwrap();
+        assert!(satisfies(&ThreeColoring::clauses(), &assignment));
+
+        assert_eq!(<Sat as Function<TriangleTwoColoring>>::Apply::assignment(), None);
+    }
+}
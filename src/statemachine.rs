@@ -0,0 +1,223 @@
+//! finite state machines where every transition is a `Function<(State, Event)>` impl
+//!
+//! `state_machine!` declares the states, the events and the transition table. Illegal
+//! transitions are not implemented, so `Machine<S>` only has methods for the events that
+//! are legal in state `S`, and `Reachable` can search the table at compile time.
+
+use std::marker::PhantomData;
+
+use crate::{
+    booleans::{Bool, If, IfOutput},
+    func_call,
+    functions::{FlatMap, Function},
+    gameoflife::Contains,
+    lists::{Cons, List, ListConcat, Nil},
+    numbers::{GEQZero, Number, PeanoEqualCmp, Successor, Zero},
+    typeif,
+};
+
+/// implemented for every state declared with `state_machine!`
+pub trait State: Default {
+    /// the transition function of the machine this state belongs to
+    type Table: TransitionTable;
+    /// index of the state in the declaration, used to compare states
+    type Id: Number;
+    const NAME: &'static str;
+}
+
+/// implemented for the transition function of every machine declared with `state_machine!`
+pub trait TransitionTable {
+    /// list of `(FromId, ToId)`, one for every transition
+    type Edges: List;
+    type NumStates: Number + GEQZero;
+}
+
+/// Runtime handle on a machine that is currently in state `S`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Machine<S: State>(S);
+
+impl<S: State> Machine<S> {
+    pub fn new() -> Self {
+        Machine(S::default())
+    }
+
+    pub fn state_name(&self) -> &'static str {
+        S::NAME
+    }
+
+    /// Take the transition for `event`. Only compiles if the transition table has one
+    pub fn fire<E>(self, _event: E) -> Machine<<S::Table as Function<(S, E)>>::Apply>
+    where
+        S::Table: Function<(S, E)>,
+        <S::Table as Function<(S, E)>>::Apply: State,
+    {
+        Machine::new()
+    }
+}
+
+/// Declare a state machine
+///
+/// ```ignore
+/// state_machine! {
+///     pub machine Door {
+///         states: [Opened, Closed, Locked],
+///         events: [Open, Close, Lock, Unlock],
+///         transitions: [
+///             (Closed, Open) => Opened as open,
+///             (Opened, Close) => Closed as close,
+///             (Closed, Lock) => Locked as lock,
+///             (Locked, Unlock) => Closed as unlock,
+///         ],
+///     }
+/// }
+/// ```
+///
+/// `Door` is the transition function, and `Machine<Closed>` gets `open` and `lock` methods.
+/// The methods are inherent impls on `Machine`, so the macro only works inside this crate
+#[macro_export]
+macro_rules! state_machine {
+    (
+        $(#[$meta:meta])*
+        $vis:vis machine $name:ident {
+            states: [$($state:ident),+ $(,)?],
+            events: [$($event:ident),+ $(,)?],
+            transitions: [$(($from:ident, $on:ident) => $to:ident as $method:ident),* $(,)?] $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name;
+
+        $(
+            #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+            $vis struct $state;
+        )+
+        $(
+            #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+            $vis struct $event;
+        )+
+
+        $crate::state_machine!(@states $name; $crate::numbers::Zero; $($state,)+);
+
+        impl $crate::statemachine::TransitionTable for $name {
+            type Edges = $crate::make_list!($((
+                <$from as $crate::statemachine::State>::Id,
+                <$to as $crate::statemachine::State>::Id
+            )),*);
+            type NumStates = <$crate::make_list!($($state),+) as $crate::lists::Length>::Length;
+        }
+
+        $(
+            impl $crate::functions::Function<($from, $on)> for $name {
+                type Apply = $to;
+            }
+            impl $crate::statemachine::Machine<$from> {
+                pub fn $method(self) -> $crate::statemachine::Machine<$to> {
+                    self.fire($on)
+                }
+            }
+        )*
+    };
+    (@states $name:ident; $id:ty; $state:ident, $($rest:ident,)*) => {
+        impl $crate::statemachine::State for $state {
+            type Table = $name;
+            type Id = $id;
+            const NAME: &'static str = stringify!($state);
+        }
+        $crate::state_machine!(@states $name; $crate::numbers::Successor<$id>; $($rest,)*);
+    };
+    (@states $name:ident; $id:ty;) => {};
+}
+
+/// (FromId, ToId) -> [ToId] if FromId is in `Set`, otherwise []
+pub struct EdgeTargetIfFrom<Set>(PhantomData<Set>);
+impl<Set, F: Number, T> Function<(F, T)> for EdgeTargetIfFrom<Set>
+where
+    Contains<PeanoEqualCmp>: Function<(Set, F)>,
+    If<func_call!(Contains<PeanoEqualCmp>[Set, F]), Cons<T, Nil>, Nil>: IfOutput,
+{
+    type Apply = typeif!(func_call!(Contains<PeanoEqualCmp>[Set, F]), Cons<T, Nil>, Nil);
+}
+
+/// Ids reachable from the ids in `Self` by taking at most `Fuel` edges
+pub trait ReachableSet<Edges, Fuel> {
+    type Output: List;
+}
+impl<Set: List, Edges> ReachableSet<Edges, Zero> for Set {
+    type Output = Set;
+}
+impl<Set: ListConcat, Edges, Fuel: Number + GEQZero> ReachableSet<Edges, Successor<Fuel>> for Set
+where
+    Edges: FlatMap<EdgeTargetIfFrom<Set>>,
+    <Edges as FlatMap<EdgeTargetIfFrom<Set>>>::Output: ListConcat,
+    Set::ConcatWith<<Edges as FlatMap<EdgeTargetIfFrom<Set>>>::Output>: ReachableSet<Edges, Fuel>,
+{
+    type Output = <Set::ConcatWith<<Edges as FlatMap<EdgeTargetIfFrom<Set>>>::Output> as ReachableSet<
+        Edges,
+        Fuel,
+    >>::Output;
+}
+
+/// `True` if some sequence of events (possibly empty) takes the machine from `From` to `To`
+pub trait Reachable<From, To> {
+    type Output: Bool;
+}
+/// Ids reachable from `From`. A path visits every state at most once, so NumStates steps are enough
+type ReachableFrom<From> = <Cons<<From as State>::Id, Nil> as ReachableSet<
+    <<From as State>::Table as TransitionTable>::Edges,
+    <<From as State>::Table as TransitionTable>::NumStates,
+>>::Output;
+
+impl<From: State, To: State<Table = From::Table>> Reachable<From, To> for (From, To)
+where
+    Cons<From::Id, Nil>: ReachableSet<
+        <From::Table as TransitionTable>::Edges,
+        <From::Table as TransitionTable>::NumStates,
+    >,
+    Contains<PeanoEqualCmp>: Function<(ReachableFrom<From>, To::Id), Apply: Bool>,
+{
+    type Output = func_call!(Contains<PeanoEqualCmp>[ReachableFrom<From>, To::Id]);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        booleans::{False, True},
+        statemachine::{Machine, Reachable},
+    };
+
+    state_machine! {
+        machine Door {
+            states: [Opened, Closed, Locked, Broken],
+            events: [Open, Close, Lock, Unlock, Kick],
+            transitions: [
+                (Closed, Open) => Opened as open,
+                (Opened, Close) => Closed as close,
+                (Closed, Lock) => Locked as lock,
+                (Locked, Unlock) => Closed as unlock,
+                (Locked, Kick) => Broken as kick,
+            ],
+        }
+    }
+
+    #[test]
+    fn test_transitions() {
+        let door: Machine<Closed> = Machine::new();
+        let door: Machine<Locked> = door.lock();
+        let door = door.unlock().open();
+        assert_eq!(door.state_name(), "Opened");
+
+        let door: Machine<Closed> = door.fire(Close);
+        assert_eq!(door.state_name(), "Closed");
+        // door.unlock() would not compile, there is no `Machine<Closed>::unlock`
+    }
+
+    #[test]
+    fn test_reachable() {
+        let _: <(Opened, Opened) as Reachable<_, _>>::Output = True;
+        let _: <(Opened, Locked) as Reachable<_, _>>::Output = True;
+        let _: <(Opened, Broken) as Reachable<_, _>>::Output = True;
+        let _: <(Locked, Opened) as Reachable<_, _>>::Output = True;
+        let _: <(Broken, Opened) as Reachable<_, _>>::Output = False;
+        let _: <(Broken, Closed) as Reachable<_, _>>::Output = False;
+    }
+}
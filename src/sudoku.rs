@@ -0,0 +1,443 @@
+//! sudoku solved inside the type system
+//!
+//! A grid is a list of rows, and every row is a list of digits (`N1` to `N9`) and `Empty`
+//! cells. The empty cells are filled one at a time: every partial grid is extended with each
+//! digit that is not yet in the row, column or box of the cell, the same way the N-Queens
+//! solver adds one queen per column.
+
+use std::marker::PhantomData;
+
+use crate::{
+    booleans::{And, Not, Or, True, False},
+    func_call,
+    functions::{Filter, FlatMap, Function, Increment, Map},
+    gameoflife::Contains,
+    lists::{Cons, Length, List, ListConcat, Nil, Reverse},
+    logic, m,
+    numbers::{GEQZero, GTZero, Number, PeanoAbsDiff, PeanoEqual, PeanoEqualCmp, PeanoLT, Range, Successor, Zero, N2, N3, N4, N9},
+};
+
+pub mod working_regular_impl {
+    /// Draw a grid with `|` and `-` between the boxes and `.` for empty cells
+    pub fn render(grid: &[Vec<Option<usize>>], box_side: usize) -> String {
+        let rows: Vec<String> = grid
+            .iter()
+            .map(|row| {
+                let boxes: Vec<String> = row
+                    .chunks(box_side)
+                    .map(|cells| {
+                        let cells: Vec<String> = cells
+                            .iter()
+                            .map(|cell| cell.map_or(".".to_string(), |digit| digit.to_string()))
+                            .collect();
+                        cells.join(" ")
+                    })
+                    .collect();
+                boxes.join(" | ")
+            })
+            .collect();
+
+        let separator = match rows.first() {
+            Some(row) => {
+                let segments: Vec<String> = row.split(" | ").map(|segment| "-".repeat(segment.len())).collect();
+                segments.join("-+-")
+            }
+            None => return String::new(),
+        };
+
+        let bands: Vec<String> = rows.chunks(box_side).map(|band| band.join("\n")).collect();
+        bands.join(&format!("\n{}\n", separator))
+    }
+}
+
+/// A cell that still has to be filled
+#[derive(Default)]
+pub struct Empty;
+/// Result of solving a grid that cannot be completed
+#[derive(Default)]
+pub struct NoSolution;
+
+/// Side of the boxes of an N x N grid
+pub trait BoxSize {
+    type Side: Number + GTZero;
+}
+impl BoxSize for N4 {
+    type Side = N2;
+}
+impl BoxSize for N9 {
+    type Side = N3;
+}
+
+/// floor(Self / D) for Self >= 0 and D > 0
+pub trait DivFloor<D: Number> {
+    type Output: Number + GEQZero;
+}
+impl<N: PeanoLT<D>, D: Number> DivFloor<D> for N
+where
+    N: DivFloorIf<m!(lt N, D), D>,
+{
+    type Output = <N as DivFloorIf<m!(lt N, D), D>>::Output;
+}
+
+pub trait DivFloorIf<LT, D: Number> {
+    type Output: Number + GEQZero;
+}
+impl<N, D: Number> DivFloorIf<True, D> for N {
+    type Output = Zero;
+}
+impl<N: PeanoAbsDiff<D>, D: Number> DivFloorIf<False, D> for N
+where
+    <N as PeanoAbsDiff<D>>::AbsDiff: DivFloor<D>,
+{
+    type Output = Successor<<<N as PeanoAbsDiff<D>>::AbsDiff as DivFloor<D>>::Output>;
+}
+
+/// A cell on row R and column C. B is the (row, column) of its box
+pub struct Slot<R, C, B>(PhantomData<(R, C, B)>);
+pub type SlotAt<Side, R, C> = Slot<R, C, (<R as DivFloor<Side>>::Output, <C as DivFloor<Side>>::Output)>;
+
+/// Digit D written in slot S
+pub struct Entry<S, D>(PhantomData<(S, D)>);
+
+/// The entries for the digits and the slots for the `Empty` cells of a row, starting at column C
+pub trait ScanRow<Side, R, C> {
+    type Entries: List + ListConcat;
+    type Slots: List + ListConcat;
+}
+impl<Side, R, C> ScanRow<Side, R, C> for Nil {
+    type Entries = Nil;
+    type Slots = Nil;
+}
+impl<Side: Number, R: DivFloor<Side>, C: DivFloor<Side> + Number + GEQZero, Rest> ScanRow<Side, R, C>
+    for Cons<Empty, Rest>
+where
+    Rest: ScanRow<Side, R, Successor<C>>,
+{
+    type Entries = <Rest as ScanRow<Side, R, Successor<C>>>::Entries;
+    type Slots = Cons<SlotAt<Side, R, C>, <Rest as ScanRow<Side, R, Successor<C>>>::Slots>;
+}
+impl<Side: Number, R: DivFloor<Side>, C: DivFloor<Side> + Number + GEQZero, D: Number + GTZero, Rest>
+    ScanRow<Side, R, C> for Cons<D, Rest>
+where
+    Rest: ScanRow<Side, R, Successor<C>>,
+{
+    type Entries = Cons<Entry<SlotAt<Side, R, C>, D>, <Rest as ScanRow<Side, R, Successor<C>>>::Entries>;
+    type Slots = <Rest as ScanRow<Side, R, Successor<C>>>::Slots;
+}
+
+/// `ScanRow` for every row of a grid, starting at row R
+pub trait ScanGrid<Side, R> {
+    type Entries: List + ListConcat;
+    type Slots: List + ListConcat;
+}
+impl<Side, R> ScanGrid<Side, R> for Nil {
+    type Entries = Nil;
+    type Slots = Nil;
+}
+impl<Side, R: Number + GEQZero, Row: ScanRow<Side, R, Zero>, Rest> ScanGrid<Side, R> for Cons<Row, Rest>
+where
+    Rest: ScanGrid<Side, Successor<R>>,
+    <Row::Entries as ListConcat>::ConcatWith<<Rest as ScanGrid<Side, Successor<R>>>::Entries>: ListConcat,
+    <Row::Slots as ListConcat>::ConcatWith<<Rest as ScanGrid<Side, Successor<R>>>::Slots>: ListConcat,
+{
+    type Entries = <Row::Entries as ListConcat>::ConcatWith<<Rest as ScanGrid<Side, Successor<R>>>::Entries>;
+    type Slots = <Row::Slots as ListConcat>::ConcatWith<<Rest as ScanGrid<Side, Successor<R>>>::Slots>;
+}
+
+/// Entry -> True if it is in the same row, column or box as the slot
+pub struct SharesUnit<S>(PhantomData<S>);
+impl<R, C, BR, BC, R2, C2, BR2, BC2, D> Function<Entry<Slot<R2, C2, (BR2, BC2)>, D>> for SharesUnit<Slot<R, C, (BR, BC)>>
+where
+    R: Number,
+    C: Number,
+    BR: Number,
+    BC: Number,
+    R2: PeanoEqual<R>,
+    C2: PeanoEqual<C>,
+    BR2: PeanoEqual<BR>,
+    BC2: PeanoEqual<BC>,
+    m!(eq R2, R): Or<m!(eq C2, C)>,
+    m!(eq BR2, BR): And<m!(eq BC2, BC)>,
+    logic!(or m!(eq R2, R), m!(eq C2, C)): Or<logic!(and m!(eq BR2, BR), m!(eq BC2, BC))>,
+{
+    type Apply = logic!(or
+        logic!(or m!(eq R2, R), m!(eq C2, C)),
+        logic!(and m!(eq BR2, BR), m!(eq BC2, BC))
+    );
+}
+
+pub struct DigitOf;
+impl<S, D> Function<Entry<S, D>> for DigitOf {
+    type Apply = D;
+}
+
+/// Digit -> True if it is not in `Used`
+pub struct NotIn<Used>(PhantomData<Used>);
+impl<Used, D: Number> Function<D> for NotIn<Used>
+where
+    Contains<PeanoEqualCmp>: Function<(Used, D), Apply: Not>,
+{
+    type Apply = <func_call!(Contains<PeanoEqualCmp>[Used, D]) as Not>::Not;
+}
+
+/// Digit -> the grid `Filled` with the digit written in slot S
+pub struct WriteDigit<S, Filled>(PhantomData<S>, PhantomData<Filled>);
+impl<S, Filled, D> Function<D> for WriteDigit<S, Filled> {
+    type Apply = Cons<Entry<S, D>, Filled>;
+}
+
+/// 1, 2, ..., N (in reverse order)
+pub type Digits<N> = <<N as Range>::Range as Map<Increment>>::Output;
+/// The digits already used in the row, column and box of S
+pub type UsedDigits<S, Filled> = <<Filled as Filter<SharesUnit<S>>>::Output as Map<DigitOf>>::Output;
+
+/// Filled -> list of the ways to write a digit from 1 to N into slot S
+pub struct FillSlot<N, S>(PhantomData<N>, PhantomData<S>);
+impl<N: Range, S, Filled> Function<Filled> for FillSlot<N, S>
+where
+    <N as Range>::Range: Map<Increment>,
+    Filled: Filter<SharesUnit<S>>,
+    <Filled as Filter<SharesUnit<S>>>::Output: Map<DigitOf>,
+    Digits<N>: Filter<NotIn<UsedDigits<S, Filled>>>,
+    <Digits<N> as Filter<NotIn<UsedDigits<S, Filled>>>>::Output: Map<WriteDigit<S, Filled>>,
+{
+    type Apply = <<Digits<N> as Filter<NotIn<UsedDigits<S, Filled>>>>::Output as Map<WriteDigit<S, Filled>>>::Output;
+}
+
+/// Fill the slots in `Todo` one after the other in each of the partial grids
+pub trait Search<N, Todo> {
+    type Output: List;
+}
+impl<N, Configs: List> Search<N, Nil> for Configs {
+    type Output = Configs;
+}
+impl<N, S, Rest, Configs: FlatMap<FillSlot<N, S>>> Search<N, Cons<S, Rest>> for Configs
+where
+    <Configs as FlatMap<FillSlot<N, S>>>::Output: Search<N, Rest>,
+{
+    type Output = <<Configs as FlatMap<FillSlot<N, S>>>::Output as Search<N, Rest>>::Output;
+}
+
+/// Replace the `Empty` cells of a row with the digits of `Entries`, in order
+pub trait FillRow<Entries> {
+    type Output: List;
+    type Remaining;
+}
+impl<Entries> FillRow<Entries> for Nil {
+    type Output = Nil;
+    type Remaining = Entries;
+}
+impl<S, D, Entries, Rest: FillRow<Entries>> FillRow<Cons<Entry<S, D>, Entries>> for Cons<Empty, Rest> {
+    type Output = Cons<D, Rest::Output>;
+    type Remaining = Rest::Remaining;
+}
+impl<D: Number, Entries, Rest: FillRow<Entries>> FillRow<Entries> for Cons<D, Rest> {
+    type Output = Cons<D, Rest::Output>;
+    type Remaining = Rest::Remaining;
+}
+
+/// `FillRow` for every row of a grid
+pub trait FillGrid<Entries> {
+    type Output: List;
+}
+impl<Entries> FillGrid<Entries> for Nil {
+    type Output = Nil;
+}
+impl<Entries, Row: FillRow<Entries>, Rest: FillGrid<Row::Remaining>> FillGrid<Entries> for Cons<Row, Rest> {
+    type Output = Cons<Row::Output, Rest::Output>;
+}
+
+/// The first of the solutions written into `Grid`, or `NoSolution`
+pub trait FirstSolution<Grid> {
+    type Output;
+}
+impl<Grid> FirstSolution<Grid> for Nil {
+    type Output = NoSolution;
+}
+impl<Grid: FillGrid<Filled>, Filled, Others> FirstSolution<Grid> for Cons<Filled, Others> {
+    type Output = <Grid as FillGrid<Filled>>::Output;
+}
+
+type SideOf<Grid> = <<Grid as Length>::Length as BoxSize>::Side;
+type Scan<Grid> = <Grid as ScanGrid<SideOf<Grid>, Zero>>::Entries;
+/// The empty slots in reverse order. Each filled slot is prepended to the entries, so the
+/// entries of a solution start with the first empty cell
+type Todo<Grid> = <<Grid as ScanGrid<SideOf<Grid>, Zero>>::Slots as Reverse>::Output;
+type Solutions<Grid> = <Cons<Scan<Grid>, Nil> as Search<<Grid as Length>::Length, Todo<Grid>>>::Output;
+
+/// Grid -> the solved grid, or `NoSolution`
+pub struct SolveSudoku;
+impl<Grid> Function<Grid> for SolveSudoku
+where
+    Grid: Length + ScanGrid<SideOf<Grid>, Zero>,
+    Grid::Length: BoxSize,
+    <Grid as ScanGrid<SideOf<Grid>, Zero>>::Slots: Reverse,
+    Cons<Scan<Grid>, Nil>: Search<Grid::Length, Todo<Grid>>,
+    Solutions<Grid>: FirstSolution<Grid>,
+{
+    type Apply = <Solutions<Grid> as FirstSolution<Grid>>::Output;
+}
+
+pub trait SudokuCell {
+    const DIGIT: Option<usize>;
+}
+impl SudokuCell for Empty {
+    const DIGIT: Option<usize> = None;
+}
+impl<D: Number> SudokuCell for D {
+    const DIGIT: Option<usize> = Some(D::VALUE as usize);
+}
+
+/// The cells of a grid as runtime values
+pub trait SudokuRows {
+    fn rows() -> Vec<Vec<Option<usize>>>;
+}
+impl SudokuRows for Nil {
+    fn rows() -> Vec<Vec<Option<usize>>> {
+        vec![]
+    }
+}
+impl<Row: SudokuRow, Rest: SudokuRows> SudokuRows for Cons<Row, Rest> {
+    fn rows() -> Vec<Vec<Option<usize>>> {
+        let mut rows = Rest::rows();
+        rows.insert(0, Row::cells());
+        rows
+    }
+}
+
+pub trait SudokuRow {
+    fn cells() -> Vec<Option<usize>>;
+}
+impl SudokuRow for Nil {
+    fn cells() -> Vec<Option<usize>> {
+        vec![]
+    }
+}
+impl<Cell: SudokuCell, Rest: SudokuRow> SudokuRow for Cons<Cell, Rest> {
+    fn cells() -> Vec<Option<usize>> {
+        let mut cells = Rest::cells();
+        cells.insert(0, Cell::DIGIT);
+        cells
+    }
+}
+
+/// Draw a grid, or the result of `SolveSudoku`
+pub trait RenderSudoku {
+    fn render() -> String;
+}
+impl RenderSudoku for NoSolution {
+    fn render() -> String {
+        "no solution".into()
+    }
+}
+impl<Grid: SudokuRows + Length> RenderSudoku for Grid
+where
+    Grid::Length: BoxSize,
+{
+    fn render() -> String {
+        working_regular_impl::render(&Grid::rows(), SideOf::<Grid>::VALUE as usize)
+    }
+}
+
+fn sudoku_type_test() {
+    use crate::{
+        make_list,
+        numbers::{N0, N1, N5, N7, N8},
+    };
+
+    let _: <N0 as DivFloor<N3>>::Output = N0::default();
+    let _: <N5 as DivFloor<N3>>::Output = N1::default();
+    let _: <N8 as DivFloor<N3>>::Output = N2::default();
+    let _: <N7 as DivFloor<N2>>::Output = N3::default();
+
+    type E = Empty;
+    type Puzzle = make_list!(
+        make_list!(N1, E, E, N4),
+        make_list!(E, N4, N1, E),
+        make_list!(N2, E, E, N3),
+        make_list!(E, N3, N2, E),
+    );
+    type Solved = make_list!(
+        make_list!(N1, N2, N3, N4),
+        make_list!(N3, N4, N1, N2),
+        make_list!(N2, N1, N4, N3),
+        make_list!(N4, N3, N2, N1),
+    );
+    let _: <SolveSudoku as Function<Puzzle>>::Apply = Solved::default();
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        functions::Function,
+        make_list,
+        numbers::{N1, N2, N3, N4},
+        sudoku::{Empty as E, RenderSudoku, SolveSudoku},
+    };
+
+    #[test]
+    fn test_4x4() {
+        type Puzzle = make_list!(
+            make_list!(E, E, N3, E),
+            make_list!(N3, E, E, N4),
+            make_list!(N2, E, E, N1),
+            make_list!(E, N1, E, E),
+        );
+        assert_eq!(
+            Puzzle::render(),
+            ". . | 3 .\n3 . | . 4\n----+----\n2 . | . 1\n. 1 | . ."
+        );
+        assert_eq!(
+            <SolveSudoku as Function<Puzzle>>::Apply::render(),
+            "1 4 | 3 2\n3 2 | 1 4\n----+----\n2 3 | 4 1\n4 1 | 2 3"
+        );
+    }
+
+    #[test]
+    fn test_no_solution() {
+        // nothing fits in the top left corner
+        type Puzzle = make_list!(
+            make_list!(E, N2, N3, E),
+            make_list!(E, N4, E, E),
+            make_list!(N1, E, E, E),
+            make_list!(E, E, E, E),
+        );
+        assert_eq!(<SolveSudoku as Function<Puzzle>>::Apply::render(), "no solution");
+    }
+
+    // needs the 1024 recursion limit that the `slow-type-tests` feature turns on
+    #[cfg(feature = "slow-type-tests")]
+    #[test]
+    fn test_9x9() {
+        use crate::numbers::{N5, N6, N7, N8, N9};
+
+        type Puzzle = make_list!(
+            make_list!(N5, N3, E, E, N7, E, E, E, E),
+            make_list!(N6, E, E, N1, N9, N5, E, E, E),
+            make_list!(E, N9, N8, E, E, E, E, N6, E),
+            make_list!(N8, E, E, E, N6, E, E, E, N3),
+            make_list!(N4, E, E, N8, E, N3, E, E, N1),
+            make_list!(N7, E, E, E, N2, E, E, E, N6),
+            make_list!(E, N6, E, E, E, E, N2, N8, E),
+            make_list!(E, E, E, N4, N1, N9, E, E, N5),
+            make_list!(E, E, E, E, N8, E, E, N7, N9),
+        );
+        assert_eq!(
+            <SolveSudoku as Function<Puzzle>>::Apply::render(),
+            [
+                "5 3 4 | 6 7 8 | 9 1 2",
+                "6 7 2 | 1 9 5 | 3 4 8",
+                "1 9 8 | 3 4 2 | 5 6 7",
+                "------+-------+------",
+                "8 5 9 | 7 6 1 | 4 2 3",
+                "4 2 6 | 8 5 3 | 7 9 1",
+                "7 1 3 | 9 2 4 | 8 5 6",
+                "------+-------+------",
+                "9 6 1 | 5 3 7 | 2 8 4",
+                "2 8 7 | 4 1 9 | 6 3 5",
+                "3 4 5 | 2 8 6 | 1 7 9",
+            ]
+            .join("\n")
+        );
+    }
+}
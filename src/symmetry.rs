@@ -0,0 +1,243 @@
+//! symmetries of the square board, used to reduce N-Queens solutions to one per symmetry class
+//!
+//! Every N-Queens solution has exactly one queen per x, so a configuration is fully described by
+//! the y of the queen in each column. A solution is kept if that sequence is the lexicographically
+//! smallest one among its 8 rotations and reflections.
+
+use std::marker::PhantomData;
+
+use crate::{
+    booleans::{And, Bool, False, Or, True},
+    functions::{AllTrue, Filter, Function, Map},
+    lists::{Cons, List, ListConcat, Nil},
+    logic, m,
+    nqueens::{NQueensSolution, Placed},
+    numbers::{GEQZero, Number, PeanoAbsDiff, PeanoEqual, PeanoLT, Range, Successor, Zero, N4},
+};
+
+pub mod working_regular_impl {
+    use crate::nqueens::working_regular_impl::solve;
+
+    /// The y of the queen in every column, from the last column down like `YSequence`
+    fn y_sequence(n: usize, queens: &[(usize, usize)]) -> Vec<usize> {
+        let mut ys = vec![0; n];
+        for &(x, y) in queens {
+            ys[n - 1 - x] = y;
+        }
+        ys
+    }
+
+    /// The same 8 images as `Images`: 4 quarter turns of the configuration and of its reflection
+    fn images(n: usize, queens: &[(usize, usize)]) -> Vec<Vec<(usize, usize)>> {
+        let rotate = |queens: &Vec<(usize, usize)>| -> Vec<_> { queens.iter().map(|&(x, y)| (y, n - 1 - x)).collect() };
+        let reflected = queens.iter().map(|&(x, y)| (n - 1 - x, y)).collect();
+        vec![queens.to_vec(), reflected]
+            .into_iter()
+            .flat_map(|start| std::iter::successors(Some(start), |config| Some(rotate(config))).take(4))
+            .collect()
+    }
+
+    /// N-Queens solutions with one representative per symmetry class, like `FundamentalSolutions`
+    pub fn fundamental_solutions(n: usize) -> Vec<Vec<(usize, usize)>> {
+        solve(n)
+            .into_iter()
+            .filter(|queens| {
+                let ys = y_sequence(n, queens);
+                images(n, queens).iter().all(|image| ys <= y_sequence(n, image))
+            })
+            .collect()
+    }
+}
+
+/// (x, y) -> (y, N - 1 - x), a quarter turn of an N x N board
+pub struct Rotate90<N>(PhantomData<N>);
+impl<M: Number + GEQZero, P, X: Number, Y: Number> Function<Placed<P, X, Y>> for Rotate90<Successor<M>>
+where
+    M: PeanoAbsDiff<X>,
+{
+    type Apply = Placed<P, Y, <M as PeanoAbsDiff<X>>::AbsDiff>;
+}
+
+/// (x, y) -> (N - 1 - x, y)
+pub struct ReflectX<N>(PhantomData<N>);
+impl<M: Number + GEQZero, P, X: Number, Y: Number> Function<Placed<P, X, Y>> for ReflectX<Successor<M>>
+where
+    M: PeanoAbsDiff<X>,
+{
+    type Apply = Placed<P, <M as PeanoAbsDiff<X>>::AbsDiff, Y>;
+}
+
+/// (x, y) -> (y, x), reflection along the main diagonal. `Images` does not need it: it is
+/// `ReflectX` followed by `Rotate90`, so the transposed configuration is already one of the 8
+pub struct Transpose;
+impl<P, X: Number, Y: Number> Function<Placed<P, X, Y>> for Transpose {
+    type Apply = Placed<P, Y, X>;
+}
+
+/// Map `T` over every piece of a configuration
+pub struct Transform<T>(PhantomData<T>);
+impl<T, Config: Map<T>> Function<Config> for Transform<T> {
+    type Apply = <Config as Map<T>>::Output;
+}
+
+/// [C, T(C), T(T(C)), ...] with `Count` configurations, where `T` is mapped over every piece
+pub trait Iterates<T, Count> {
+    type Output: List + ListConcat;
+}
+impl<Config, T> Iterates<T, Zero> for Config {
+    type Output = Nil;
+}
+impl<Config: Map<T>, T, Count: Number + GEQZero> Iterates<T, Successor<Count>> for Config
+where
+    <Config as Map<T>>::Output: Iterates<T, Count>,
+{
+    type Output = Cons<Config, <<Config as Map<T>>::Output as Iterates<T, Count>>::Output>;
+}
+
+/// The 8 images of a configuration under the symmetries of an N x N board, including itself
+pub trait Images<N> {
+    type Output: List;
+}
+impl<N, Config> Images<N> for Config
+where
+    Config: Iterates<Rotate90<N>, N4> + Map<ReflectX<N>>,
+    <Config as Map<ReflectX<N>>>::Output: Iterates<Rotate90<N>, N4>,
+{
+    type Output = <<Config as Iterates<Rotate90<N>, N4>>::Output as ListConcat>::ConcatWith<
+        <<Config as Map<ReflectX<N>>>::Output as Iterates<Rotate90<N>, N4>>::Output,
+    >;
+}
+
+/// y of the piece in column `X`
+pub trait FindY<X> {
+    type Output: Number;
+}
+impl<X, P, QX: PeanoEqual<X>, QY: Number, Rest> FindY<X> for Cons<Placed<P, QX, QY>, Rest>
+where
+    X: Number,
+    QX: Number,
+    Rest: FindYIf<<QX as PeanoEqual<X>>::Equal, X, QY>,
+{
+    type Output = <Rest as FindYIf<<QX as PeanoEqual<X>>::Equal, X, QY>>::Output;
+}
+
+pub trait FindYIf<Found, X, Y> {
+    type Output: Number;
+}
+impl<Rest, X, Y: Number> FindYIf<True, X, Y> for Rest {
+    type Output = Y;
+}
+impl<Rest: FindY<X>, X, Y> FindYIf<False, X, Y> for Rest {
+    type Output = <Rest as FindY<X>>::Output;
+}
+
+pub struct YAt<Config>(PhantomData<Config>);
+impl<Config: FindY<X>, X> Function<X> for YAt<Config> {
+    type Apply = <Config as FindY<X>>::Output;
+}
+
+/// Config -> the y of every column of an N x N board
+pub struct YSequence<N>(PhantomData<N>);
+impl<N: Range, Config> Function<Config> for YSequence<N>
+where
+    <N as Range>::Range: Map<YAt<Config>>,
+{
+    type Apply = <<N as Range>::Range as Map<YAt<Config>>>::Output;
+}
+
+/// Lexicographic <= on equally long lists of numbers
+pub trait LexLEQ<Other> {
+    type Output: Bool;
+}
+impl LexLEQ<Nil> for Nil {
+    type Output = True;
+}
+impl<A, AS, B, BS> LexLEQ<Cons<B, BS>> for Cons<A, AS>
+where
+    A: PeanoLT<B> + PeanoEqual<B>,
+    B: Number,
+    AS: LexLEQ<BS>,
+    m!(eq A, B): And<<AS as LexLEQ<BS>>::Output>,
+    m!(lt A, B): Or<logic!(and m!(eq A, B), <AS as LexLEQ<BS>>::Output)>,
+{
+    // a < b, or a == b and the rest is <=
+    type Output = logic!(or m!(lt A, B), logic!(and m!(eq A, B), <AS as LexLEQ<BS>>::Output));
+}
+
+/// Image -> is the y sequence of `Config` <= the one of Image
+pub struct NotAfter<N, Config>(PhantomData<N>, PhantomData<Config>);
+impl<N, Config, Image> Function<Image> for NotAfter<N, Config>
+where
+    YSequence<N>: Function<Config> + Function<Image>,
+    <YSequence<N> as Function<Config>>::Apply: LexLEQ<<YSequence<N> as Function<Image>>::Apply>,
+{
+    type Apply = <<YSequence<N> as Function<Config>>::Apply as LexLEQ<<YSequence<N> as Function<Image>>::Apply>>::Output;
+}
+
+/// Config -> True if the configuration is the representative of its symmetry class
+pub struct IsCanonical<N>(PhantomData<N>);
+impl<N, Config> Function<Config> for IsCanonical<N>
+where
+    Config: Images<N>,
+    <Config as Images<N>>::Output: Map<NotAfter<N, Config>>,
+    <<Config as Images<N>>::Output as Map<NotAfter<N, Config>>>::Output: AllTrue,
+{
+    type Apply = <<<Config as Images<N>>::Output as Map<NotAfter<N, Config>>>::Output as AllTrue>::Output;
+}
+
+/// N-Queens solutions with one representative per class of rotations and reflections
+pub struct FundamentalSolutions;
+impl<N> Function<N> for FundamentalSolutions
+where
+    NQueensSolution: Function<N>,
+    <NQueensSolution as Function<N>>::Apply: Filter<IsCanonical<N>>,
+{
+    type Apply = <<NQueensSolution as Function<N>>::Apply as Filter<IsCanonical<N>>>::Output;
+}
+
+fn symmetry_type_test() {
+    use crate::{
+        lists::Length,
+        make_list,
+        nqueens::Queen,
+        numbers::{N0, N1, N2, N3, N5, N6},
+    };
+
+    let _: <Rotate90<N4> as Function<Queen<N0, N1>>>::Apply = Queen::<N1, N3>::default();
+    let _: <ReflectX<N4> as Function<Queen<N0, N1>>>::Apply = Queen::<N3, N1>::default();
+    let _: <Transpose as Function<Queen<N0, N1>>>::Apply = Queen::<N1, N0>::default();
+    type Reflected = <ReflectX<N4> as Function<Queen<N0, N1>>>::Apply;
+    let _: <Rotate90<N4> as Function<Reflected>>::Apply = Queen::<N1, N0>::default();
+    type Config = make_list!(Queen<N0, N1>, Queen<N2, N3>);
+    let _: <Transform<Transpose> as Function<Config>>::Apply = <make_list!(Queen<N1, N0>, Queen<N3, N2>)>::default();
+
+    type Count<N> = <<FundamentalSolutions as Function<N>>::Apply as Length>::Length;
+    let _: Count<N1> = N1::default();
+    let _: Count<N2> = N0::default();
+    let _: Count<N4> = N1::default();
+    let _: Count<N5> = N2::default();
+    let _: Count<N6> = N1::default();
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        nqueens::test::{as_set, type_level},
+        numbers::{N4, N5, N6},
+        symmetry::{working_regular_impl::fundamental_solutions, FundamentalSolutions},
+    };
+
+    #[test]
+    fn test_runtime_counts() {
+        let counts: Vec<usize> = (1..=8).map(|n| fundamental_solutions(n).len()).collect();
+        // 92 solutions for N = 8, but only 12 up to rotation and reflection
+        assert_eq!(counts, vec![1, 0, 0, 1, 2, 1, 6, 12]);
+    }
+
+    #[test]
+    fn test_type_level_matches_runtime() {
+        assert_eq!(type_level::<FundamentalSolutions, N4>(), as_set(fundamental_solutions(4)));
+        assert_eq!(type_level::<FundamentalSolutions, N5>(), as_set(fundamental_solutions(5)));
+        assert_eq!(type_level::<FundamentalSolutions, N6>(), as_set(fundamental_solutions(6)));
+    }
+}
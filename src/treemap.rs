@@ -0,0 +1,380 @@
+//! ordered maps as AVL trees
+//!
+//! A map is a binary search tree built from `Node<L, K, V, R>` and `Leaf`, ordered by a
+//! comparator `Cmp`: a `Predicate<(A, B)>` that is True when key A comes before key B. Keys
+//! where neither comes first are the same key. Every operation takes the comparator, and the
+//! ones that change the tree rebalance it, so the heights of the two children of any node
+//! differ by at most one and the depth stays logarithmic in the size.
+
+use std::marker::PhantomData;
+
+use crate::{
+    booleans::{False, True},
+    functions::{Function, Predicate},
+    lists::{Cons, Length, List, ListConcat, Nil},
+    m,
+    numbers::{GEQZero, Number, PeanoLT, Successor, Zero},
+    option::{None, OptionMap, Some, TypeOption, UnwrapOr},
+};
+
+#[derive(Default)]
+pub struct Leaf;
+#[derive(Default)]
+pub struct Node<L, K, V, R>(PhantomData<L>, PhantomData<K>, PhantomData<V>, PhantomData<R>);
+
+/// (A, B) -> A < B, the comparator for maps keyed by `Number`s
+pub struct NumberOrder;
+impl<A: PeanoLT<B>, B: Number> Function<(A, B)> for NumberOrder {
+    type Apply = m!(lt A, B);
+}
+
+/// Does key `A` come before key `B`
+pub type Before<Cmp, A, B> = <Cmp as Predicate<(A, B)>>::BoolApply;
+
+/// The larger of two heights
+pub trait MaxHeight<Other> {
+    type Output: Number + GEQZero;
+}
+impl<Other: Number + GEQZero> MaxHeight<Other> for Zero {
+    type Output = Other;
+}
+impl<N: Number + GEQZero> MaxHeight<Zero> for Successor<N> {
+    type Output = Successor<N>;
+}
+impl<N: Number + GEQZero + MaxHeight<M>, M: Number + GEQZero> MaxHeight<Successor<M>> for Successor<N> {
+    type Output = Successor<N::Output>;
+}
+
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a TreeMap",
+    label = "expected a tree of `Node<L, K, V, R>` and `Leaf`"
+)]
+pub trait TreeMap {
+    type Height: Number + GEQZero;
+}
+impl TreeMap for Leaf {
+    type Height = Zero;
+}
+impl<L: TreeMap, K, V, R: TreeMap> TreeMap for Node<L, K, V, R>
+where
+    L::Height: MaxHeight<R::Height>,
+{
+    type Height = Successor<<L::Height as MaxHeight<R::Height>>::Output>;
+}
+
+/// Restore the AVL invariant of a node whose children differ in height by at most two
+pub trait Balance {
+    type Output: TreeMap;
+}
+impl<L: TreeMap, K, V, R: TreeMap> Balance for Node<L, K, V, R>
+where
+    Successor<R::Height>: PeanoLT<L::Height>,
+    Successor<L::Height>: PeanoLT<R::Height>,
+    Self: BalanceIf<m!(lt Successor<R::Height>, L::Height), m!(lt Successor<L::Height>, R::Height)>,
+{
+    type Output = <Self as BalanceIf<
+        m!(lt Successor<R::Height>, L::Height),
+        m!(lt Successor<L::Height>, R::Height),
+    >>::Output;
+}
+
+/// `Balance` once it is known which child, if any, is too high
+pub trait BalanceIf<LeftHeavy, RightHeavy> {
+    type Output: TreeMap;
+}
+impl<T: TreeMap> BalanceIf<False, False> for T {
+    type Output = T;
+}
+impl<LL: TreeMap, LK, LV, LR: TreeMap, K, V, R> BalanceIf<True, False> for Node<Node<LL, LK, LV, LR>, K, V, R>
+where
+    LL::Height: PeanoLT<LR::Height>,
+    Self: RotateRight<m!(lt LL::Height, LR::Height)>,
+{
+    type Output = <Self as RotateRight<m!(lt LL::Height, LR::Height)>>::Output;
+}
+impl<L, K, V, RL: TreeMap, RK, RV, RR: TreeMap> BalanceIf<False, True> for Node<L, K, V, Node<RL, RK, RV, RR>>
+where
+    RR::Height: PeanoLT<RL::Height>,
+    Self: RotateLeft<m!(lt RR::Height, RL::Height)>,
+{
+    type Output = <Self as RotateLeft<m!(lt RR::Height, RL::Height)>>::Output;
+}
+
+/// Lift the left child, first rotating it left if its inner child is the higher one
+pub trait RotateRight<Double> {
+    type Output: TreeMap;
+}
+impl<LL, LK, LV, LR, K, V, R> RotateRight<False> for Node<Node<LL, LK, LV, LR>, K, V, R>
+where
+    Node<LL, LK, LV, Node<LR, K, V, R>>: TreeMap,
+{
+    type Output = Node<LL, LK, LV, Node<LR, K, V, R>>;
+}
+impl<LL, LK, LV, LRL, LRK, LRV, LRR, K, V, R> RotateRight<True>
+    for Node<Node<LL, LK, LV, Node<LRL, LRK, LRV, LRR>>, K, V, R>
+where
+    Node<Node<LL, LK, LV, LRL>, LRK, LRV, Node<LRR, K, V, R>>: TreeMap,
+{
+    type Output = Node<Node<LL, LK, LV, LRL>, LRK, LRV, Node<LRR, K, V, R>>;
+}
+
+/// Lift the right child, first rotating it right if its inner child is the higher one
+pub trait RotateLeft<Double> {
+    type Output: TreeMap;
+}
+impl<L, K, V, RL, RK, RV, RR> RotateLeft<False> for Node<L, K, V, Node<RL, RK, RV, RR>>
+where
+    Node<Node<L, K, V, RL>, RK, RV, RR>: TreeMap,
+{
+    type Output = Node<Node<L, K, V, RL>, RK, RV, RR>;
+}
+impl<L, K, V, RLL, RLK, RLV, RLR, RK, RV, RR> RotateLeft<True>
+    for Node<L, K, V, Node<Node<RLL, RLK, RLV, RLR>, RK, RV, RR>>
+where
+    Node<Node<L, K, V, RLL>, RLK, RLV, Node<RLR, RK, RV, RR>>: TreeMap,
+{
+    type Output = Node<Node<L, K, V, RLL>, RLK, RLV, Node<RLR, RK, RV, RR>>;
+}
+
+/// The map with `K` bound to `V`, replacing the old value if `K` is already there
+pub trait Insert<Cmp, K, V> {
+    type Output: TreeMap;
+}
+impl<Cmp, K, V> Insert<Cmp, K, V> for Leaf {
+    type Output = Node<Leaf, K, V, Leaf>;
+}
+impl<Cmp: Predicate<(K, NK)> + Predicate<(NK, K)>, K, V, L, NK, NV, R> Insert<Cmp, K, V> for Node<L, NK, NV, R>
+where
+    Self: InsertIf<Before<Cmp, K, NK>, Before<Cmp, NK, K>, Cmp, K, V>,
+{
+    type Output = <Self as InsertIf<Before<Cmp, K, NK>, Before<Cmp, NK, K>, Cmp, K, V>>::Output;
+}
+
+/// `Insert` once it is known on which side of the node `K` goes
+pub trait InsertIf<KeyFirst, NodeFirst, Cmp, K, V> {
+    type Output: TreeMap;
+}
+impl<Cmp, K, V, L: Insert<Cmp, K, V>, NK, NV, R> InsertIf<True, False, Cmp, K, V> for Node<L, NK, NV, R>
+where
+    Node<L::Output, NK, NV, R>: Balance,
+{
+    type Output = <Node<L::Output, NK, NV, R> as Balance>::Output;
+}
+impl<Cmp, K, V, L, NK, NV, R: Insert<Cmp, K, V>> InsertIf<False, True, Cmp, K, V> for Node<L, NK, NV, R>
+where
+    Node<L, NK, NV, R::Output>: Balance,
+{
+    type Output = <Node<L, NK, NV, R::Output> as Balance>::Output;
+}
+impl<Cmp, K, V, L, NK, NV, R> InsertIf<False, False, Cmp, K, V> for Node<L, NK, NV, R>
+where
+    Node<L, K, V, R>: TreeMap,
+{
+    type Output = Node<L, K, V, R>;
+}
+
+/// Every (K, V) of `Entries` inserted in order
+pub trait InsertAll<Cmp, Entries> {
+    type Output: TreeMap;
+}
+impl<Cmp, T: TreeMap> InsertAll<Cmp, Nil> for T {
+    type Output = T;
+}
+impl<Cmp, K, V, Rest, T: Insert<Cmp, K, V>> InsertAll<Cmp, Cons<(K, V), Rest>> for T
+where
+    T::Output: InsertAll<Cmp, Rest>,
+{
+    type Output = <T::Output as InsertAll<Cmp, Rest>>::Output;
+}
+
+/// `Some` of the value bound to `K`, or `None`
+pub trait Lookup<Cmp, K> {
+    type Output: TypeOption;
+}
+impl<Cmp, K> Lookup<Cmp, K> for Leaf {
+    type Output = None;
+}
+impl<Cmp: Predicate<(K, NK)> + Predicate<(NK, K)>, K, L, NK, NV, R> Lookup<Cmp, K> for Node<L, NK, NV, R>
+where
+    Self: LookupIf<Before<Cmp, K, NK>, Before<Cmp, NK, K>, Cmp, K>,
+{
+    type Output = <Self as LookupIf<Before<Cmp, K, NK>, Before<Cmp, NK, K>, Cmp, K>>::Output;
+}
+
+/// `Lookup` once it is known on which side of the node `K` is
+pub trait LookupIf<KeyFirst, NodeFirst, Cmp, K> {
+    type Output: TypeOption;
+}
+impl<Cmp, K, L: Lookup<Cmp, K>, NK, NV, R> LookupIf<True, False, Cmp, K> for Node<L, NK, NV, R> {
+    type Output = L::Output;
+}
+impl<Cmp, K, L, NK, NV, R: Lookup<Cmp, K>> LookupIf<False, True, Cmp, K> for Node<L, NK, NV, R> {
+    type Output = R::Output;
+}
+impl<Cmp, K, L, NK, NV, R> LookupIf<False, False, Cmp, K> for Node<L, NK, NV, R> {
+    type Output = Some<NV>;
+}
+
+/// The map without `K`. Unchanged if `K` is not there
+pub trait Remove<Cmp, K> {
+    type Output: TreeMap;
+}
+impl<Cmp, K> Remove<Cmp, K> for Leaf {
+    type Output = Leaf;
+}
+impl<Cmp: Predicate<(K, NK)> + Predicate<(NK, K)>, K, L, NK, NV, R> Remove<Cmp, K> for Node<L, NK, NV, R>
+where
+    Self: RemoveIf<Before<Cmp, K, NK>, Before<Cmp, NK, K>, Cmp, K>,
+{
+    type Output = <Self as RemoveIf<Before<Cmp, K, NK>, Before<Cmp, NK, K>, Cmp, K>>::Output;
+}
+
+/// `Remove` once it is known on which side of the node `K` is
+pub trait RemoveIf<KeyFirst, NodeFirst, Cmp, K> {
+    type Output: TreeMap;
+}
+impl<Cmp, K, L: Remove<Cmp, K>, NK, NV, R> RemoveIf<True, False, Cmp, K> for Node<L, NK, NV, R>
+where
+    Node<L::Output, NK, NV, R>: Balance,
+{
+    type Output = <Node<L::Output, NK, NV, R> as Balance>::Output;
+}
+impl<Cmp, K, L, NK, NV, R: Remove<Cmp, K>> RemoveIf<False, True, Cmp, K> for Node<L, NK, NV, R>
+where
+    Node<L, NK, NV, R::Output>: Balance,
+{
+    type Output = <Node<L, NK, NV, R::Output> as Balance>::Output;
+}
+impl<Cmp, K, T: RemoveRoot> RemoveIf<False, False, Cmp, K> for T {
+    type Output = T::Output;
+}
+
+/// The node without its own key: its left child if it has no right one, otherwise the smallest
+/// entry of the right child takes its place
+pub trait RemoveRoot {
+    type Output: TreeMap;
+}
+impl<L: TreeMap, K, V> RemoveRoot for Node<L, K, V, Leaf> {
+    type Output = L;
+}
+impl<L, K, V, RL, RK, RV, RR> RemoveRoot for Node<L, K, V, Node<RL, RK, RV, RR>>
+where
+    Node<RL, RK, RV, RR>: RemoveMin,
+    Node<L, MinKey<Node<RL, RK, RV, RR>>, MinValue<Node<RL, RK, RV, RR>>, WithoutMin<Node<RL, RK, RV, RR>>>: Balance,
+{
+    type Output = <Node<
+        L,
+        MinKey<Node<RL, RK, RV, RR>>,
+        MinValue<Node<RL, RK, RV, RR>>,
+        WithoutMin<Node<RL, RK, RV, RR>>,
+    > as Balance>::Output;
+}
+
+/// The leftmost entry of a non-empty tree, and the tree without it
+pub trait RemoveMin {
+    type Key;
+    type Value;
+    type Rest: TreeMap;
+}
+impl<K, V, R: TreeMap> RemoveMin for Node<Leaf, K, V, R> {
+    type Key = K;
+    type Value = V;
+    type Rest = R;
+}
+impl<LL, LK, LV, LR, K, V, R> RemoveMin for Node<Node<LL, LK, LV, LR>, K, V, R>
+where
+    Node<LL, LK, LV, LR>: RemoveMin,
+    Node<WithoutMin<Node<LL, LK, LV, LR>>, K, V, R>: Balance,
+{
+    type Key = MinKey<Node<LL, LK, LV, LR>>;
+    type Value = MinValue<Node<LL, LK, LV, LR>>;
+    type Rest = <Node<WithoutMin<Node<LL, LK, LV, LR>>, K, V, R> as Balance>::Output;
+}
+
+type MinKey<T> = <T as RemoveMin>::Key;
+type MinValue<T> = <T as RemoveMin>::Value;
+type WithoutMin<T> = <T as RemoveMin>::Rest;
+
+/// Bind `K` to `F` of its old value, or to `Default` if it is not there
+pub trait Upsert<Cmp, K, Default, F> {
+    type Output: TreeMap;
+}
+impl<Cmp, K, Default, F, T: Lookup<Cmp, K>> Upsert<Cmp, K, Default, F> for T
+where
+    <T as Lookup<Cmp, K>>::Output: OptionMap<F>,
+    <<T as Lookup<Cmp, K>>::Output as OptionMap<F>>::Output: UnwrapOr<Default>,
+    T: Insert<Cmp, K, Updated<T, Cmp, K, Default, F>>,
+{
+    type Output = <T as Insert<Cmp, K, Updated<T, Cmp, K, Default, F>>>::Output;
+}
+
+type Updated<T, Cmp, K, Default, F> =
+    <<<T as Lookup<Cmp, K>>::Output as OptionMap<F>>::Output as UnwrapOr<Default>>::Output;
+
+/// The (K, V) entries in key order
+pub trait ToList {
+    type Output: List + ListConcat;
+}
+impl ToList for Leaf {
+    type Output = Nil;
+}
+impl<L: ToList, K, V, R: ToList> ToList for Node<L, K, V, R>
+where
+    <L::Output as ListConcat>::ConcatWith<Cons<(K, V), R::Output>>: ListConcat,
+{
+    type Output = <L::Output as ListConcat>::ConcatWith<Cons<(K, V), R::Output>>;
+}
+
+/// Number of entries
+pub trait Size {
+    type Size: Number + GEQZero;
+}
+impl<T: ToList<Output: Length>> Size for T {
+    type Size = <T::Output as Length>::Length;
+}
+
+fn treemap_type_test() {
+    use crate::{
+        functions::Increment,
+        lists::{Reverse, Zip},
+        make_list,
+        numbers::{Range, N0, N1, N2, N3, N4, N5, N7, N15},
+    };
+
+    type Keys = <<N15 as Range>::Range as Reverse>::Output;
+    type Entries = <Keys as Zip<Keys>>::Output;
+    // inserting keys in order would make a list of height 15 without rebalancing
+    type Ascending = <Leaf as InsertAll<NumberOrder, Entries>>::Output;
+    type Descending = <Leaf as InsertAll<NumberOrder, <Entries as Reverse>::Output>>::Output;
+    let _: <Ascending as TreeMap>::Height = N4::default();
+    let _: <Descending as TreeMap>::Height = N4::default();
+    let _: <Ascending as Size>::Size = N15::default();
+    let _: <Descending as ToList>::Output = Entries::default();
+
+    let _: <Ascending as Lookup<NumberOrder, N7>>::Output = Some::<N7>::default();
+    let _: <Ascending as Lookup<NumberOrder, N15>>::Output = None;
+    let _: <Leaf as Lookup<NumberOrder, N0>>::Output = None;
+
+    type Small = <Leaf as InsertAll<NumberOrder, make_list!((N3, N0), (N1, N0), (N4, N0), (N0, N0))>>::Output;
+    type Replaced = <Small as Insert<NumberOrder, N1, N5>>::Output;
+    let _: <Replaced as ToList>::Output = <make_list!((N0, N0), (N1, N5), (N3, N0), (N4, N0))>::default();
+    let _: <Replaced as Size>::Size = N4::default();
+
+    type WithoutRoot = <Small as Remove<NumberOrder, N3>>::Output;
+    let _: <WithoutRoot as ToList>::Output = <make_list!((N0, N0), (N1, N0), (N4, N0))>::default();
+    let _: <WithoutRoot as TreeMap>::Height = N2::default();
+    let _: <<Small as Remove<NumberOrder, N2>>::Output as ToList>::Output = <Small as ToList>::Output::default();
+    // removing from the short side rotates the other side up
+    type Rebalanced = <<Small as Remove<NumberOrder, N4>>::Output as TreeMap>::Height;
+    let _: Rebalanced = N2::default();
+    let _: <<Ascending as Remove<NumberOrder, N7>>::Output as Size>::Size = <m!(sub N15, N1)>::default();
+
+    type Counted = <<Small as Upsert<NumberOrder, N1, N1, Increment>>::Output as Upsert<NumberOrder, N2, N1, Increment>>::Output;
+    let _: <Counted as ToList>::Output = <make_list!((N0, N0), (N1, N1), (N2, N1), (N3, N0), (N4, N0))>::default();
+    let _: <<Counted as Upsert<NumberOrder, N2, N1, Increment>>::Output as Lookup<NumberOrder, N2>>::Output =
+        Some::<N2>::default();
+
+    let _: <Node<Leaf, N0, N0, Leaf> as TreeMap>::Height = N1::default();
+    let _: Before<NumberOrder, N0, N1> = True;
+    let _: <N3 as MaxHeight<N5>>::Output = N5::default();
+}
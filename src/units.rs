@@ -0,0 +1,280 @@
+//! units of measure checked by the type system
+//!
+//! A `Quantity<T, D>` is a value of type `T` with dimension `D`. The dimension holds a
+//! type-level exponent for each SI base unit, so multiplying quantities adds exponents,
+//! dividing subtracts them, and adding quantities of different dimensions does not compile.
+
+use std::{
+    fmt,
+    marker::PhantomData,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use crate::{
+    m,
+    numbers::{Number, PeanoAdd, PeanoSub},
+};
+
+/// Exponents of length, mass, time, current, temperature, amount of substance and luminosity
+pub struct Dim<L, M, T, I, Th, N, J>(PhantomData<(L, M, T, I, Th, N, J)>);
+
+pub trait Dimension {
+    const EXPONENTS: [isize; 7];
+}
+impl<L: Number, M: Number, T: Number, I: Number, Th: Number, N: Number, J: Number> Dimension
+    for Dim<L, M, T, I, Th, N, J>
+{
+    const EXPONENTS: [isize; 7] = [L::VALUE, M::VALUE, T::VALUE, I::VALUE, Th::VALUE, N::VALUE, J::VALUE];
+}
+
+/// Dimension of the product of two quantities
+pub trait DimMul<Other> {
+    type Output: Dimension;
+}
+impl<L1, M1, T1, I1, Th1, N1_, J1, L2, M2, T2, I2, Th2, N2_, J2> DimMul<Dim<L2, M2, T2, I2, Th2, N2_, J2>>
+    for Dim<L1, M1, T1, I1, Th1, N1_, J1>
+where
+    L1: PeanoAdd<L2>,
+    M1: PeanoAdd<M2>,
+    T1: PeanoAdd<T2>,
+    I1: PeanoAdd<I2>,
+    Th1: PeanoAdd<Th2>,
+    N1_: PeanoAdd<N2_>,
+    J1: PeanoAdd<J2>,
+    L2: Number,
+    M2: Number,
+    T2: Number,
+    I2: Number,
+    Th2: Number,
+    N2_: Number,
+    J2: Number,
+{
+    type Output = Dim<
+        m!(add L1, L2),
+        m!(add M1, M2),
+        m!(add T1, T2),
+        m!(add I1, I2),
+        m!(add Th1, Th2),
+        m!(add N1_, N2_),
+        m!(add J1, J2),
+    >;
+}
+
+/// Dimension of the quotient of two quantities
+pub trait DimDiv<Other> {
+    type Output: Dimension;
+}
+impl<L1, M1, T1, I1, Th1, N1_, J1, L2, M2, T2, I2, Th2, N2_, J2> DimDiv<Dim<L2, M2, T2, I2, Th2, N2_, J2>>
+    for Dim<L1, M1, T1, I1, Th1, N1_, J1>
+where
+    L1: PeanoSub<L2>,
+    M1: PeanoSub<M2>,
+    T1: PeanoSub<T2>,
+    I1: PeanoSub<I2>,
+    Th1: PeanoSub<Th2>,
+    N1_: PeanoSub<N2_>,
+    J1: PeanoSub<J2>,
+    L2: Number,
+    M2: Number,
+    T2: Number,
+    I2: Number,
+    Th2: Number,
+    N2_: Number,
+    J2: Number,
+{
+    type Output = Dim<
+        m!(sub L1, L2),
+        m!(sub M1, M2),
+        m!(sub T1, T2),
+        m!(sub I1, I2),
+        m!(sub Th1, Th2),
+        m!(sub N1_, N2_),
+        m!(sub J1, J2),
+    >;
+}
+
+/// Dimensions of the SI base units and some derived units
+pub mod dims {
+    use super::Dim;
+    use crate::numbers::{Negative, Zero as Z, N1 as P1, N2 as P2, N3 as P3};
+
+    type M1 = Negative<P1>;
+    type M2 = Negative<P2>;
+    type M3 = Negative<P3>;
+
+    pub type Dimensionless = Dim<Z, Z, Z, Z, Z, Z, Z>;
+    pub type Length = Dim<P1, Z, Z, Z, Z, Z, Z>;
+    pub type Mass = Dim<Z, P1, Z, Z, Z, Z, Z>;
+    pub type Time = Dim<Z, Z, P1, Z, Z, Z, Z>;
+    pub type Current = Dim<Z, Z, Z, P1, Z, Z, Z>;
+    pub type Temperature = Dim<Z, Z, Z, Z, P1, Z, Z>;
+    pub type Amount = Dim<Z, Z, Z, Z, Z, P1, Z>;
+    pub type Luminosity = Dim<Z, Z, Z, Z, Z, Z, P1>;
+
+    pub type Area = Dim<P2, Z, Z, Z, Z, Z, Z>;
+    pub type Volume = Dim<P3, Z, Z, Z, Z, Z, Z>;
+    pub type Frequency = Dim<Z, Z, M1, Z, Z, Z, Z>;
+    pub type Velocity = Dim<P1, Z, M1, Z, Z, Z, Z>;
+    pub type Acceleration = Dim<P1, Z, M2, Z, Z, Z, Z>;
+    pub type Force = Dim<P1, P1, M2, Z, Z, Z, Z>;
+    pub type Pressure = Dim<M1, P1, M2, Z, Z, Z, Z>;
+    pub type Energy = Dim<P2, P1, M2, Z, Z, Z, Z>;
+    pub type Power = Dim<P2, P1, M3, Z, Z, Z, Z>;
+    pub type Charge = Dim<Z, Z, P1, P1, Z, Z, Z>;
+    pub type Voltage = Dim<P2, P1, M3, M1, Z, Z, Z>;
+}
+
+/// `value` measured in the SI base units of `D`
+pub struct Quantity<T, D>(pub T, PhantomData<D>);
+
+// implemented by hand so that `D` does not need to implement these traits
+impl<T: Clone, D> Clone for Quantity<T, D> {
+    fn clone(&self) -> Self {
+        Quantity(self.0.clone(), PhantomData)
+    }
+}
+impl<T: Copy, D> Copy for Quantity<T, D> {}
+impl<T: PartialEq, D> PartialEq for Quantity<T, D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+impl<T: PartialOrd, D> PartialOrd for Quantity<T, D> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+impl<T: fmt::Debug, D: Dimension> fmt::Debug for Quantity<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Quantity").field(&self.0).field(&D::EXPONENTS).finish()
+    }
+}
+
+impl<T, D: Dimension> Quantity<T, D> {
+    pub fn new(value: T) -> Self {
+        Quantity(value, PhantomData)
+    }
+
+    pub fn value(self) -> T {
+        self.0
+    }
+}
+
+pub type Scalar<T = f64> = Quantity<T, dims::Dimensionless>;
+pub type Meters<T = f64> = Quantity<T, dims::Length>;
+pub type Kilograms<T = f64> = Quantity<T, dims::Mass>;
+pub type Seconds<T = f64> = Quantity<T, dims::Time>;
+pub type Amperes<T = f64> = Quantity<T, dims::Current>;
+pub type Kelvin<T = f64> = Quantity<T, dims::Temperature>;
+pub type Moles<T = f64> = Quantity<T, dims::Amount>;
+pub type Candelas<T = f64> = Quantity<T, dims::Luminosity>;
+pub type SquareMeters<T = f64> = Quantity<T, dims::Area>;
+pub type CubicMeters<T = f64> = Quantity<T, dims::Volume>;
+pub type Hertz<T = f64> = Quantity<T, dims::Frequency>;
+pub type MetersPerSecond<T = f64> = Quantity<T, dims::Velocity>;
+pub type MetersPerSecondSquared<T = f64> = Quantity<T, dims::Acceleration>;
+pub type Newtons<T = f64> = Quantity<T, dims::Force>;
+pub type Pascals<T = f64> = Quantity<T, dims::Pressure>;
+pub type Joules<T = f64> = Quantity<T, dims::Energy>;
+pub type Watts<T = f64> = Quantity<T, dims::Power>;
+pub type Coulombs<T = f64> = Quantity<T, dims::Charge>;
+pub type Volts<T = f64> = Quantity<T, dims::Voltage>;
+
+/// Quantities can only be added if their dimensions are the same type
+impl<T: Add<Output = T>, D: Dimension> Add for Quantity<T, D> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Quantity::new(self.0 + other.0)
+    }
+}
+
+impl<T: Sub<Output = T>, D: Dimension> Sub for Quantity<T, D> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Quantity::new(self.0 - other.0)
+    }
+}
+
+impl<T: Neg<Output = T>, D: Dimension> Neg for Quantity<T, D> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Quantity::new(-self.0)
+    }
+}
+
+impl<T: Mul<Output = T>, D1: DimMul<D2>, D2> Mul<Quantity<T, D2>> for Quantity<T, D1> {
+    type Output = Quantity<T, D1::Output>;
+    fn mul(self, other: Quantity<T, D2>) -> Self::Output {
+        Quantity::new(self.0 * other.0)
+    }
+}
+
+impl<T: Div<Output = T>, D1: DimDiv<D2>, D2> Div<Quantity<T, D2>> for Quantity<T, D1> {
+    type Output = Quantity<T, D1::Output>;
+    fn div(self, other: Quantity<T, D2>) -> Self::Output {
+        Quantity::new(self.0 / other.0)
+    }
+}
+
+/// prints the value followed by its SI base units, e.g. `9.81 m·s^-2`
+impl<T: fmt::Display, D: Dimension> fmt::Display for Quantity<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const SYMBOLS: [&str; 7] = ["m", "kg", "s", "A", "K", "mol", "cd"];
+
+        let units: Vec<String> = SYMBOLS
+            .iter()
+            .zip(D::EXPONENTS.iter())
+            .filter(|(_, &exponent)| exponent != 0)
+            .map(|(symbol, &exponent)| match exponent {
+                1 => symbol.to_string(),
+                _ => format!("{}^{}", symbol, exponent),
+            })
+            .collect();
+
+        if units.is_empty() {
+            write!(f, "{}", self.0)
+        } else {
+            write!(f, "{} {}", self.0, units.join("·"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::units::{
+        Joules, Kilograms, Meters, MetersPerSecond, MetersPerSecondSquared, Newtons, Scalar, Seconds, Watts,
+    };
+
+    #[test]
+    fn test_derived_units() {
+        let distance: Meters = Meters::new(100.0);
+        let time: Seconds = Seconds::new(20.0);
+        let speed: MetersPerSecond = distance / time;
+        assert_eq!(speed.value(), 5.0);
+
+        let acceleration: MetersPerSecondSquared = speed / time;
+        let force: Newtons = Kilograms::new(80.0) * acceleration;
+        assert_eq!(force.value(), 20.0);
+
+        let work: Joules = force * distance;
+        let power: Watts = work / time;
+        assert_eq!(power.value(), 100.0);
+
+        let ratio: Scalar = distance / Meters::new(50.0);
+        assert_eq!(ratio.value(), 2.0);
+    }
+
+    #[test]
+    fn test_add_same_dimension() {
+        let total = Meters::new(1.5) + Meters::new(2.0) - Meters::new(0.5);
+        assert_eq!(total, Meters::new(3.0));
+        assert!(Seconds::new(1.0) < Seconds::new(2.0));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Meters::new(2.0).to_string(), "2 m");
+        assert_eq!(Newtons::new(1.5).to_string(), "1.5 m·kg·s^-2");
+        assert_eq!(Scalar::new(3).to_string(), "3");
+    }
+}
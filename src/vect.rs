@@ -0,0 +1,188 @@
+//! vectors whose length is a type-level `Number`
+
+use std::{convert::TryInto, marker::PhantomData};
+
+use crate::{
+    m,
+    numbers::{GEQZero, GTZero, LiftNumber, Number, NumberWrapper, PeanoAdd, Successor, Zero},
+};
+
+/// A vector of `T` that always holds exactly `N` items
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Vect<T, N: Number + GEQZero>(Vec<T>, PhantomData<N>);
+
+impl<T> Vect<T, Zero> {
+    pub fn new() -> Self {
+        Vect(Vec::new(), PhantomData)
+    }
+}
+
+impl<T> Default for Vect<T, Zero> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, N: Number + GEQZero> Vect<T, N> {
+    pub const LEN: usize = N::VALUE as usize;
+
+    /// Only used internally, callers must make sure `items` has `N` items
+    fn from_vec_unchecked(items: Vec<T>) -> Self {
+        debug_assert_eq!(items.len(), Self::LEN);
+        Vect(items, PhantomData)
+    }
+
+    pub fn len(&self) -> usize {
+        Self::LEN
+    }
+
+    pub fn is_empty(&self) -> bool {
+        Self::LEN == 0
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.0
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.0
+    }
+
+    pub fn push(mut self, item: T) -> Vect<T, Successor<N>> {
+        self.0.push(item);
+        Vect::from_vec_unchecked(self.0)
+    }
+
+    pub fn concat<M: Number + GEQZero>(mut self, other: Vect<T, M>) -> Vect<T, m!(add N, M)>
+    where
+        N: PeanoAdd<M, Val: GEQZero>,
+    {
+        self.0.extend(other.0);
+        Vect::from_vec_unchecked(self.0)
+    }
+
+    /// Both vectors have length `N`, so nothing gets dropped
+    pub fn zip<U>(self, other: Vect<U, N>) -> Vect<(T, U), N> {
+        Vect::from_vec_unchecked(self.0.into_iter().zip(other.0).collect())
+    }
+
+    pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> Vect<U, N> {
+        Vect::from_vec_unchecked(self.0.into_iter().map(f).collect())
+    }
+}
+
+impl<T, N: Number + GTZero> Vect<T, N> {
+    pub fn head(&self) -> &T {
+        &self.0[0]
+    }
+
+    pub fn last(&self) -> &T {
+        &self.0[Self::LEN - 1]
+    }
+}
+
+impl<T, N: Number + GEQZero> Vect<T, Successor<N>> {
+    /// Remove the last item
+    pub fn pop(mut self) -> (Vect<T, N>, T) {
+        // N + 1 > 0, so there is always something to pop
+        let item = self.0.pop().unwrap();
+        (Vect::from_vec_unchecked(self.0), item)
+    }
+
+    /// Everything except the first item. This shifts the other items, so it is O(N) like
+    /// `Vec::remove(0)`
+    pub fn tail(mut self) -> Vect<T, N> {
+        self.0.remove(0);
+        Vect::from_vec_unchecked(self.0)
+    }
+}
+
+/// Only for arrays of 0 to 16 items, the lengths `LiftNumber` is implemented for
+impl<T, N: Number + GEQZero, const L: usize> From<[T; L]> for Vect<T, N>
+where
+    NumberWrapper<L>: LiftNumber<Value = N>,
+{
+    fn from(items: [T; L]) -> Self {
+        Vect::from_vec_unchecked(items.into())
+    }
+}
+
+/// Only for arrays of 0 to 16 items, the lengths `LiftNumber` is implemented for
+impl<T, N: Number + GEQZero, const L: usize> From<Vect<T, N>> for [T; L]
+where
+    NumberWrapper<L>: LiftNumber<Value = N>,
+{
+    fn from(items: Vect<T, N>) -> Self {
+        match items.0.try_into() {
+            Ok(array) => array,
+            Err(_) => unreachable!("a Vect always holds N items"),
+        }
+    }
+}
+
+impl<T, N: Number + GEQZero> std::ops::Index<usize> for Vect<T, N> {
+    type Output = T;
+    fn index(&self, index: usize) -> &T {
+        &self.0[index]
+    }
+}
+
+impl<T, N: Number + GEQZero> std::ops::IndexMut<usize> for Vect<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.0[index]
+    }
+}
+
+impl<T, N: Number + GEQZero> IntoIterator for Vect<T, N> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        numbers::{N0, N2, N3, N5},
+        vect::Vect,
+    };
+
+    #[test]
+    fn test_push_pop() {
+        let v: Vect<i32, N0> = Vect::new();
+        let v: Vect<i32, N2> = v.push(1).push(2);
+        assert_eq!(v.as_slice(), &[1, 2]);
+        assert_eq!(*v.head(), 1);
+        assert_eq!(*v.last(), 2);
+
+        let (v, item) = v.pop();
+        assert_eq!(item, 2);
+        let (v, item) = v.pop();
+        assert_eq!(item, 1);
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn test_concat_zip() {
+        let a: Vect<i32, N3> = [1, 2, 3].into();
+        let b: Vect<i32, N2> = [4, 5].into();
+        let joined: Vect<i32, N5> = a.concat(b);
+        assert_eq!(joined.len(), 5);
+
+        let letters: Vect<char, N5> = ['a', 'b', 'c', 'd', 'e'].into();
+        let zipped = joined.zip(letters);
+        assert_eq!(zipped.tail().into_vec(), vec![(2, 'b'), (3, 'c'), (4, 'd'), (5, 'e')]);
+    }
+
+    #[test]
+    fn test_arrays() {
+        let v: Vect<u8, N3> = [7, 8, 9].into();
+        let doubled: [u8; 3] = v.map(|x| x * 2).into();
+        assert_eq!(doubled, [14, 16, 18]);
+    }
+}